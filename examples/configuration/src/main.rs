@@ -1,7 +1,7 @@
 use fregate::axum::{routing::get, Router};
 use fregate::config::FileFormat;
 use fregate::{bootstrap, tokio};
-use fregate::{AppConfig, Application, ConfigSource, Empty};
+use fregate::{AppConfig, Application, ConfigKey, ConfigSchema, ConfigSource, Empty, ValueKind};
 use serde::Deserialize;
 
 async fn handler() -> &'static str {
@@ -14,6 +14,18 @@ struct Custom {
     number: u32,
 }
 
+impl ConfigSchema for Custom {
+    fn config_keys() -> Vec<ConfigKey> {
+        vec![ConfigKey {
+            key: "number",
+            kind: ValueKind::U32,
+            required: true,
+            default: None,
+            description: "Some custom number.",
+        }]
+    }
+}
+
 #[tokio::main]
 async fn main() {
     std::env::set_var("TEST_PORT", "3333");
@@ -51,6 +63,10 @@ async fn main() {
     let _conf: AppConfig<Custom> =
         AppConfig::default_with("./examples/configuration/app.yaml", "TEST").unwrap();
 
+    // JSON Schema and fully commented example config of AppConfig<Custom>, might be checked in and diffed on CI
+    let _schema = AppConfig::<Custom>::json_schema();
+    let _example = AppConfig::<Custom>::example_config();
+
     Application::new(conf_0)
         .router(Router::new().route("/", get(handler)))
         .serve()
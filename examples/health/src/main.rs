@@ -41,4 +41,5 @@ async fn main() {
     curl http://0.0.0.0:8000/health
     curl http://0.0.0.0:8000/live
     curl http://0.0.0.0:8000/ready
+    curl http://0.0.0.0:8000/startup
 */
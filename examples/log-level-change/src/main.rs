@@ -52,3 +52,9 @@ async fn handler() -> &'static str {
 /*
     curl http://0.0.0.0:8000
 */
+
+/*
+    Levels might also be changed with management endpoint, ttl (in milliseconds) is optional:
+    curl http://0.0.0.0:8000/loglevel
+    curl -X PUT -H "Content-Type: application/json" -d '{"log": "debug", "trace": "debug", "ttl": 60000}' http://0.0.0.0:8000/loglevel
+*/
@@ -1,23 +1,39 @@
 pub mod health;
+pub(crate) mod lifecycle;
+pub(crate) mod limits;
+pub mod listener;
 pub(crate) mod management;
+mod running;
+pub(crate) mod shutdown;
+pub mod supervisor;
 
 #[cfg(feature = "tls")]
 pub(crate) mod tls;
 pub mod version;
 
-use crate::application::health::{AlwaysReadyAndAlive, HealthExt};
+pub use running::RunningApplication;
+
+use crate::application::health::{AlwaysReadyAndAlive, GrpcHealthService, HealthExt};
 use crate::application::version::{DefaultVersion, VersionExt};
-use crate::configuration::{AppConfig, Empty};
-use crate::error::Result;
-use crate::management::build_management_router;
+use crate::configuration::{AppConfig, Empty, HttpConfig};
+use crate::error::{Error, Result};
+use crate::management::{build_feature_router, build_management_router};
 use crate::middleware::trace_request;
+use crate::observability::register_metrics_tasks;
 use axum::middleware::from_fn;
 use axum::Router;
+use hyper::server::Builder;
 use hyper::Server;
-use std::fmt::{Debug, Formatter};
+use lifecycle::{Lifecycle, Startup};
+use limits::{ConnectionLimit, InFlightLimit, LimitedIncoming};
+use listener::{Incoming, Listener, ListenerAddr};
+use shutdown::{ApplicationHealth, Shutdown, ShutdownExecutor};
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::signal;
+use supervisor::{CancellationToken, RestartPolicy, Supervisor};
+use tokio::try_join;
 use tracing::info;
 
 /// Application to set up HTTP server with given config [`AppConfig`]
@@ -28,6 +44,11 @@ pub struct Application<H = AlwaysReadyAndAlive, T = Empty, V = DefaultVersion> {
     router: Option<Router>,
     metrics_callback: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
     use_default_trace_layer: bool,
+    lifecycle: Lifecycle,
+    supervisor: Supervisor,
+    shutdown: Shutdown,
+    listener: Option<Listener>,
+    management_listener: Option<Listener>,
 }
 
 impl<H: Debug, T: Debug, V: Debug> Debug for Application<H, T, V> {
@@ -39,6 +60,11 @@ impl<H: Debug, T: Debug, V: Debug> Debug for Application<H, T, V> {
             metrics_callback,
             use_default_trace_layer,
             version,
+            lifecycle,
+            supervisor,
+            shutdown,
+            listener,
+            management_listener,
         } = self;
         f.debug_struct("Application")
             .field("config", config)
@@ -54,6 +80,11 @@ impl<H: Debug, T: Debug, V: Debug> Debug for Application<H, T, V> {
                     &"None"
                 },
             )
+            .field("lifecycle", lifecycle)
+            .field("supervisor", supervisor)
+            .field("shutdown", shutdown)
+            .field("listener", listener)
+            .field("management_listener", management_listener)
             .finish()
     }
 }
@@ -61,20 +92,17 @@ impl<H: Debug, T: Debug, V: Debug> Debug for Application<H, T, V> {
 impl<T> Application<T> {
     /// Creates new Application with health checks always returning [200 OK]
     pub fn new(config: AppConfig<T>) -> Application<AlwaysReadyAndAlive, T, DefaultVersion> {
-        Application::<AlwaysReadyAndAlive, T, DefaultVersion> {
-            config: Arc::new(config),
-            health_indicator: AlwaysReadyAndAlive,
-            version: DefaultVersion,
-            router: None,
-            metrics_callback: None,
-            use_default_trace_layer: true,
-        }
+        Application::new_from_arced(Arc::new(config))
     }
 
     /// Creates new Application with health checks always returning [200 OK]
     pub fn new_from_arced(
         config: Arc<AppConfig<T>>,
     ) -> Application<AlwaysReadyAndAlive, T, DefaultVersion> {
+        let shutdown = Shutdown::new(config.shutdown_cfg.clone());
+        let supervisor = Supervisor::default();
+        register_metrics_tasks(&supervisor, &config.observability_cfg);
+
         Application::<AlwaysReadyAndAlive, T, DefaultVersion> {
             config,
             health_indicator: AlwaysReadyAndAlive,
@@ -82,12 +110,18 @@ impl<T> Application<T> {
             router: None,
             metrics_callback: None,
             use_default_trace_layer: true,
+            lifecycle: Lifecycle::default(),
+            supervisor,
+            shutdown,
+            listener: None,
+            management_listener: None,
         }
     }
 }
 
 impl<H, T, V> Application<H, T, V> {
-    /// Set up new health indicator
+    /// Set up new health indicator.\
+    /// Services returned by [`Application::grpc_health_service`] before this call keep using previous indicator.
     pub fn health_indicator<Hh: HealthExt>(self, health: Hh) -> Application<Hh, T, V> {
         let Self {
             config,
@@ -96,6 +130,11 @@ impl<H, T, V> Application<H, T, V> {
             metrics_callback,
             use_default_trace_layer,
             version,
+            lifecycle,
+            supervisor,
+            shutdown,
+            listener,
+            management_listener,
         } = self;
 
         Application::<Hh, T, V> {
@@ -105,6 +144,11 @@ impl<H, T, V> Application<H, T, V> {
             metrics_callback,
             use_default_trace_layer,
             version,
+            lifecycle,
+            supervisor,
+            shutdown,
+            listener,
+            management_listener,
         }
     }
 
@@ -117,6 +161,11 @@ impl<H, T, V> Application<H, T, V> {
             metrics_callback,
             use_default_trace_layer,
             version: _,
+            lifecycle,
+            supervisor,
+            shutdown,
+            listener,
+            management_listener,
         } = self;
 
         Application::<H, T, Vv> {
@@ -126,6 +175,11 @@ impl<H, T, V> Application<H, T, V> {
             metrics_callback,
             use_default_trace_layer,
             version,
+            lifecycle,
+            supervisor,
+            shutdown,
+            listener,
+            management_listener,
         }
     }
 
@@ -138,6 +192,60 @@ impl<H, T, V> Application<H, T, V> {
         }
     }
 
+    /// Returns [`GrpcHealthService`] reporting status of the whole server from application health,
+    /// so it reports `NOT_SERVING` until warm-up finishes and once shutdown has started, same as ready endpoint.\
+    /// Health indicator is captured when this fn is called, so it must be called after [`Application::health_indicator`],
+    /// otherwise service keeps reporting previously set indicator, [`AlwaysReadyAndAlive`] by default.
+    /// Warm-up, shutdown and background tasks are shared with application and might be registered before or after.
+    /// Example:
+    /// ```no_run
+    /// use fregate::axum::Router;
+    /// use fregate::extensions::RouterTonicExt;
+    /// use fregate::health::AlwaysReadyAndAlive;
+    /// use fregate::{tokio, AppConfig, Application};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let app = Application::new(AppConfig::default()).health_indicator(AlwaysReadyAndAlive);
+    ///     let grpc_health = app.grpc_health_service();
+    ///
+    ///     app.router(Router::from_tonic_service(grpc_health))
+    ///         .serve()
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn grpc_health_service(&self) -> GrpcHealthService
+    where
+        H: HealthExt,
+    {
+        GrpcHealthService::new(ApplicationHealth::new(
+            self.health_indicator.clone(),
+            self.shutdown.clone(),
+            self.supervisor.clone(),
+            self.lifecycle.startup_gate(),
+        ))
+    }
+
+    /// Set up pre-opened [`Listener`] to accept connections on instead of binding to [`AppConfig::host`] and [`AppConfig::port`].
+    #[must_use]
+    pub fn listener(self, listener: Listener) -> Self {
+        Self {
+            listener: Some(listener),
+            ..self
+        }
+    }
+
+    /// Set up pre-opened [`Listener`] to serve management endpoints on.\
+    /// Has priority over [`ManagementConfig::port`](crate::ManagementConfig::port).
+    #[must_use]
+    pub fn management_listener(self, listener: Listener) -> Self {
+        Self {
+            management_listener: Some(listener),
+            ..self
+        }
+    }
+
     /// Set up callback which will be called before metrics will render.
     #[must_use]
     pub fn metrics_callback(self, metrics_callback: impl Fn() + Send + Sync + 'static) -> Self {
@@ -147,6 +255,148 @@ impl<H, T, V> Application<H, T, V> {
         }
     }
 
+    /// Set up future which triggers graceful shutdown.\
+    /// Termination signals (Ctrl+C, SIGTERM) still trigger shutdown, whichever comes first.
+    #[must_use]
+    pub fn with_shutdown(mut self, signal: impl Future<Output = ()> + Send + 'static) -> Self {
+        self.lifecycle.set_shutdown(signal);
+        self
+    }
+
+    /// Set up hook to be called once all listeners are bound and before serving starts.\
+    /// Hooks are called sequentially in order they were added.
+    #[must_use]
+    pub fn on_start<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.lifecycle.push_on_start(hook);
+        self
+    }
+
+    /// Set up hook to be called once all connections are drained.\
+    /// Hooks are called sequentially in order they were added and before [`AppConfig`] is dropped,
+    /// so logs written in hooks are flushed by [`AppConfig::worker_guard`].
+    /// Example:
+    /// ```no_run
+    /// use fregate::{tokio, AppConfig, Application};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+    ///
+    ///     Application::new(AppConfig::default())
+    ///         .with_shutdown(async move {
+    ///             let _ = receiver.await;
+    ///         })
+    ///         .on_start(|| async move {
+    ///             // might be sent from background task on fatal error.
+    ///             let _ = sender.send(());
+    ///         })
+    ///         .on_shutdown(|| async {
+    ///             // flush producers, close connection pools, etc.
+    ///         })
+    ///         .serve()
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    #[must_use]
+    pub fn on_shutdown<F, Fut>(mut self, hook: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.lifecycle.push_on_shutdown(hook);
+        self
+    }
+
+    /// Register warm-up future (cache preload, migrations, etc.) started right after `on_start` hooks.\
+    /// Until all registered warm-up futures finish, startup and ready endpoints respond with
+    /// [`503 Service Unavailable`](axum::http::StatusCode::SERVICE_UNAVAILABLE). If any of them panics, application never reports started.
+    /// Example:
+    /// ```no_run
+    /// use fregate::{tokio, AppConfig, Application};
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Application::new(AppConfig::default())
+    ///         .warm_up(async {
+    ///             // preload caches.
+    ///             tokio::time::sleep(Duration::from_secs(5)).await;
+    ///         })
+    ///         .serve()
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    #[must_use]
+    pub fn warm_up(mut self, warm_up: impl Future<Output = ()> + Send + 'static) -> Self {
+        self.lifecycle.push_warm_up(warm_up);
+        self
+    }
+
+    /// Register background task supervised by Application with default [`RestartPolicy`].\
+    /// See [`Application::background_task_with_policy`].
+    #[must_use]
+    pub fn background_task<F, Fut, E>(self, name: &str, task: F) -> Self
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        self.background_task_with_policy(name, RestartPolicy::default(), task)
+    }
+
+    /// Register background task supervised by Application.\
+    /// Task is spawned once all listeners are bound and restarted according to given [`RestartPolicy`].\
+    /// Task names are unique, if `name` is already registered `#<n>` suffix is appended, e.g. `heartbeat#2`.
+    /// On shutdown [`CancellationToken`] passed to the task is cancelled and Application waits for the task to finish.\
+    /// Liveness endpoint responds with [`503 Service Unavailable`](axum::http::StatusCode::SERVICE_UNAVAILABLE) and state of all tasks
+    /// if any task failed and won't be restarted. Number of restarts is exported as `background_task_restarts` counter.
+    /// Example:
+    /// ```no_run
+    /// use fregate::supervisor::{Restart, RestartPolicy};
+    /// use fregate::{tokio, AppConfig, Application};
+    /// use std::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let policy = RestartPolicy {
+    ///         restart: Restart::Always,
+    ///         ..Default::default()
+    ///     };
+    ///
+    ///     Application::new(AppConfig::default())
+    ///         .background_task_with_policy("heartbeat", policy, |token| async move {
+    ///             while !token.is_cancelled() {
+    ///                 tokio::time::sleep(Duration::from_secs(1)).await;
+    ///             }
+    ///             Ok::<_, String>(())
+    ///         })
+    ///         .serve()
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    #[must_use]
+    pub fn background_task_with_policy<F, Fut, E>(
+        self,
+        name: &str,
+        policy: RestartPolicy,
+        task: F,
+    ) -> Self
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        self.supervisor.register(name, policy, task);
+        self
+    }
+
     /// Example:
     /// In this case [`trace_request`] is not attached to Application so no default tracing/metrics/logging for incoming requests
     /// ```no_run
@@ -169,15 +419,74 @@ impl<H, T, V> Application<H, T, V> {
         }
     }
 
-    /// Start serving at specified host and port in [AppConfig] accepting both HTTP1 and HTTP2
+    /// Start serving at specified host and port in [AppConfig] or on [`Listener`] if set, accepting both HTTP1 and HTTP2.\
+    /// If [`ManagementConfig::port`](crate::ManagementConfig::port) or management [`Listener`] is set, management endpoints are served by a separate listener.\
+    /// Application is served within returned future, dropping it stops the application.
     pub async fn serve(self) -> Result<()>
     where
         H: HealthExt,
         V: VersionExt<T>,
         T: Send + Sync + 'static,
     {
-        let (router, application_socket) = self.prepare_router();
-        run_service(&application_socket, router).await
+        self.start()?.serving.await
+    }
+
+    /// Same as [`Application::serve`], but returns [`RunningApplication`] handle once listeners are bound
+    /// and serves in background.
+    pub async fn bind(self) -> Result<RunningApplication>
+    where
+        H: HealthExt,
+        V: VersionExt<T>,
+        T: Send + Sync + 'static,
+    {
+        Ok(self.start()?.spawn())
+    }
+
+    /// Binds listeners and returns future serving the application.
+    fn start(mut self) -> Result<Bound<impl Future<Output = Result<()>> + Send + 'static>>
+    where
+        H: HealthExt,
+        V: VersionExt<T>,
+        T: Send + Sync + 'static,
+    {
+        let lifecycle = std::mem::take(&mut self.lifecycle);
+        let supervisor = self.supervisor.clone();
+        let config = self.config.clone();
+        let shutdown = self.shutdown.clone();
+        let http_cfg = self.config.http_cfg;
+        let limit = ConnectionLimit::new(self.config.limits_cfg.max_connections);
+        let (router, listener, management) = self.prepare(&shutdown, lifecycle.startup())?;
+
+        let incoming = bind(listener, "http", &http_cfg)?;
+        let management = bind_management(management, &http_cfg)?;
+
+        let local_addr = incoming.local_addr();
+        let management_addr = management
+            .as_ref()
+            .map(|(_, incoming)| incoming.local_addr());
+
+        let trigger = lifecycle.trigger();
+        let serving = async move {
+            let servers = async {
+                try_join!(
+                    run_service(incoming, router, &shutdown, &http_cfg, limit),
+                    run_management(management, &shutdown, &http_cfg),
+                )
+                .map(|_| ())
+            };
+
+            // routers holding AppConfig are dropped once servers stop, this keeps AppConfig
+            // and its worker_guard alive until on_shutdown hooks finish.
+            let _config = config;
+            lifecycle.run(shutdown.clone(), supervisor, servers).await
+        };
+
+        Ok(Bound {
+            local_addr,
+            management_addr,
+            trigger,
+            serving,
+        })
     }
 
     /// Serve TLS
@@ -188,7 +497,29 @@ impl<H, T, V> Application<H, T, V> {
         V: VersionExt<T>,
         T: Send + Sync + 'static,
     {
-        use crate::error::Error;
+        self.start_tls().await?.serving.await
+    }
+
+    /// Same as [`Application::serve_tls`], but returns [`RunningApplication`] handle once listeners are bound
+    /// and serves in background.
+    #[cfg(feature = "tls")]
+    pub async fn bind_tls(self) -> Result<RunningApplication>
+    where
+        H: HealthExt,
+        V: VersionExt<T>,
+        T: Send + Sync + 'static,
+    {
+        Ok(self.start_tls().await?.spawn())
+    }
+
+    /// Reads TLS certificate and key, binds listeners and returns future serving the application.
+    #[cfg(feature = "tls")]
+    async fn start_tls(mut self) -> Result<Bound<impl Future<Output = Result<()>> + Send + 'static>>
+    where
+        H: HealthExt,
+        V: VersionExt<T>,
+        T: Send + Sync + 'static,
+    {
         use futures_util::TryFutureExt;
         use std::fmt;
         use tokio::{fs, try_join};
@@ -218,24 +549,82 @@ impl<H, T, V> Application<H, T, V> {
             fs::read(tls_key_path).map_err(cant_load("key"))
         )?;
 
-        let (router, application_socket) = self.prepare_router();
-
-        tls::run_service(
-            &application_socket,
-            router,
+        let lifecycle = std::mem::take(&mut self.lifecycle);
+        let supervisor = self.supervisor.clone();
+        let config = self.config.clone();
+        let shutdown = self.shutdown.clone();
+        let http_cfg = self.config.http_cfg;
+        let limit = ConnectionLimit::new(self.config.limits_cfg.max_connections);
+        let (router, listener, management) = self.prepare(&shutdown, lifecycle.startup())?;
+
+        let listener = match listener {
+            Listener::Tcp(listener) => listener,
+            #[cfg(unix)]
+            Listener::Unix(_) => {
+                return Err(Error::CustomError(
+                    "TLS is supported only over TCP listener.".to_owned(),
+                ))
+            }
+        };
+
+        let local_addr = ListenerAddr::Tcp(listener.local_addr()?);
+        let incoming = tls::bind(
+            listener,
             tls_handshake_timeout,
             tls_cert,
             tls_key,
-        )
-        .await
+            http_cfg,
+            limit,
+        )?;
+        let management = bind_management(management, &http_cfg)?;
+        let management_addr = management
+            .as_ref()
+            .map(|(_, incoming)| incoming.local_addr());
+
+        let trigger = lifecycle.trigger();
+        let serving = async move {
+            let servers = async {
+                try_join!(
+                    tls::run_service(incoming, router, &shutdown, &http_cfg),
+                    run_management(management, &shutdown, &http_cfg),
+                )
+                .map(|_| ())
+            };
+
+            // routers holding AppConfig are dropped once servers stop, this keeps AppConfig
+            // and its worker_guard alive until on_shutdown hooks finish.
+            let _config = config;
+            lifecycle.run(shutdown.clone(), supervisor, servers).await
+        };
+
+        Ok(Bound {
+            local_addr,
+            management_addr,
+            trigger,
+            serving,
+        })
     }
 
-    fn prepare_router(self) -> (Router, SocketAddr)
+    /// Returns application router with its listener and, if management listener or port is configured,
+    /// management router with its own listener. Binds configured sockets if no listeners were set.
+    fn prepare(
+        self,
+        shutdown: &Shutdown,
+        startup: Startup,
+    ) -> Result<(Router, Listener, Management<Listener>)>
     where
         H: HealthExt,
         V: VersionExt<T>,
         T: Send + Sync + 'static,
     {
+        let http_cfg = &self.config.http_cfg;
+        if http_cfg.http1_only && http_cfg.http2_only {
+            return Err(Error::CustomError(
+                "server.http.http1.only and server.http.http2.only can't be set together."
+                    .to_owned(),
+            ));
+        }
+
         let app_router = self
             .router
             .map(|router| {
@@ -253,51 +642,188 @@ impl<H, T, V> Application<H, T, V> {
             })
             .unwrap_or_default();
 
-        let router = build_management_router(
+        let in_flight = shutdown.clone();
+        let app_router = app_router.layer(from_fn(move |req, next| {
+            in_flight.clone().track_in_flight(req, next)
+        }));
+
+        let app_router = match InFlightLimit::new(&self.config.limits_cfg) {
+            Some(in_flight_limit) => app_router.layer(from_fn(move |req, next| {
+                in_flight_limit.clone().limit(req, next)
+            })),
+            None => app_router,
+        };
+
+        let feature_routes = build_feature_router(&self.config.management_cfg, &self.supervisor);
+        let management_router = build_management_router(
             &self.config,
-            self.health_indicator,
+            ApplicationHealth::new(
+                self.health_indicator,
+                shutdown.clone(),
+                self.supervisor.clone(),
+                startup,
+            ),
             self.version,
             self.metrics_callback,
-        )
-        .merge(app_router);
+            feature_routes,
+            shutdown.clone(),
+        );
+
+        let listener = match self.listener {
+            Some(listener) => listener,
+            None => bind_socket(SocketAddr::new(self.config.host, self.config.port))?,
+        };
+
+        let management_cfg = &self.config.management_cfg;
+        let management_listener = match (self.management_listener, management_cfg.port) {
+            (Some(listener), _) => Some(listener),
+            (None, Some(port)) => {
+                let host = management_cfg.host.unwrap_or(self.config.host);
+                Some(bind_socket(SocketAddr::new(host, port))?)
+            }
+            (None, None) => None,
+        };
+
+        // requests served over Unix domain socket have no remote address to check.
+        #[cfg(unix)]
+        if management_cfg.auth.allowed_ips.is_some()
+            && matches!(
+                management_listener.as_ref().unwrap_or(&listener),
+                Listener::Unix(_)
+            )
+        {
+            return Err(Error::CustomError(
+                "management.auth.allowed.ips can't be used when management endpoints are served on Unix domain socket."
+                    .to_owned(),
+            ));
+        }
 
-        let application_socket = SocketAddr::new(self.config.host, self.config.port);
-        (router, application_socket)
+        let prepared = match management_listener {
+            Some(management_listener) => (
+                app_router,
+                listener,
+                Some((management_router, management_listener)),
+            ),
+            None => (management_router.merge(app_router), listener, None),
+        };
+
+        Ok(prepared)
     }
 }
 
-#[allow(clippy::expect_used)]
-async fn shutdown_signal() {
-    let ctrl_c = async {
-        signal::ctrl_c()
-            .await
-            .expect("failed to install Ctrl+C handler");
-    };
+/// Application with bound listeners and future serving it.
+struct Bound<F> {
+    local_addr: ListenerAddr,
+    management_addr: Option<ListenerAddr>,
+    trigger: CancellationToken,
+    serving: F,
+}
 
-    #[cfg(unix)]
-    let terminate = async {
-        signal::unix::signal(signal::unix::SignalKind::terminate())
-            .expect("failed to install SIGTERM signal handler")
-            .recv()
-            .await;
-    };
+impl<F> Bound<F>
+where
+    F: Future<Output = Result<()>> + Send + 'static,
+{
+    fn spawn(self) -> RunningApplication {
+        RunningApplication::new(
+            self.local_addr,
+            self.management_addr,
+            self.trigger,
+            tokio::spawn(self.serving),
+        )
+    }
+}
 
-    #[cfg(not(unix))]
-    let terminate = std::future::pending::<()>();
+/// Management router with listener it is served on, if management endpoints are served separately.
+type Management<L> = Option<(Router, L)>;
 
-    tokio::select! {
-        _ = ctrl_c => {},
-        _ = terminate => {},
+fn bind_socket(socket: SocketAddr) -> Result<Listener> {
+    Ok(Listener::Tcp(std::net::TcpListener::bind(socket)?))
+}
+
+fn bind(listener: Listener, scheme: &str, http_cfg: &HttpConfig) -> Result<Incoming> {
+    let mut incoming = listener.into_incoming()?;
+    if let Incoming::Tcp(incoming) = &mut incoming {
+        incoming.set_nodelay(http_cfg.tcp_nodelay);
+        incoming.set_keepalive(http_cfg.tcp_keepalive);
+    }
+    info!(target: "server", "Started: {scheme}://{}", incoming.local_addr());
+
+    Ok(incoming)
+}
+
+fn bind_management(
+    management: Management<Listener>,
+    http_cfg: &HttpConfig,
+) -> Result<Management<Incoming>> {
+    management
+        .map(|(router, listener)| Ok((router, bind(listener, "http", http_cfg)?)))
+        .transpose()
+}
+
+/// Creates [`Server`] builder with [`HttpConfig`] applied, serving connections on [`ShutdownExecutor`].
+fn server_builder<I>(
+    incoming: I,
+    shutdown: &Shutdown,
+    http_cfg: &HttpConfig,
+) -> Builder<I, ShutdownExecutor> {
+    let mut builder = Server::builder(incoming)
+        .executor(shutdown.executor())
+        .http2_keep_alive_interval(http_cfg.http2_keep_alive_interval)
+        .http2_max_concurrent_streams(http_cfg.http2_max_concurrent_streams);
+
+    // `false` resets protocol to default, so only enabled option is applied.
+    if http_cfg.http1_only {
+        builder = builder.http1_only(true);
+    }
+    if http_cfg.http2_only {
+        builder = builder.http2_only(true);
+    }
+    if let Some(read_timeout) = http_cfg.http1_header_read_timeout {
+        builder = builder.http1_header_read_timeout(read_timeout);
+    }
+    if let Some(keep_alive_timeout) = http_cfg.http2_keep_alive_timeout {
+        builder = builder.http2_keep_alive_timeout(keep_alive_timeout);
     }
 
-    info!("Termination signal, starting shutdown...");
+    builder
 }
 
-async fn run_service(socket: &SocketAddr, router: Router) -> Result<()> {
-    let app = router.into_make_service_with_connect_info::<SocketAddr>();
-    let server = Server::bind(socket).serve(app);
+async fn run_service(
+    incoming: Incoming,
+    router: Router,
+    shutdown: &Shutdown,
+    http_cfg: &HttpConfig,
+    limit: ConnectionLimit,
+) -> Result<()> {
+    match incoming {
+        Incoming::Tcp(incoming) => {
+            let incoming = LimitedIncoming::new(incoming, limit);
+            let app = router.into_make_service_with_connect_info::<SocketAddr>();
+            let server = server_builder(incoming, shutdown, http_cfg).serve(app);
+
+            Ok(server.with_graceful_shutdown(shutdown.subscribe()).await?)
+        }
+        #[cfg(unix)]
+        Incoming::Unix(incoming) => {
+            let incoming = LimitedIncoming::new(incoming, limit);
+            let server =
+                server_builder(incoming, shutdown, http_cfg).serve(router.into_make_service());
 
-    info!(target: "server", "Started: http://{socket}");
+            Ok(server.with_graceful_shutdown(shutdown.subscribe()).await?)
+        }
+    }
+}
 
-    Ok(server.with_graceful_shutdown(shutdown_signal()).await?)
+async fn run_management(
+    management: Management<Incoming>,
+    shutdown: &Shutdown,
+    http_cfg: &HttpConfig,
+) -> Result<()> {
+    match management {
+        Some((router, incoming)) => {
+            let limit = ConnectionLimit::default();
+            run_service(incoming, router, shutdown, http_cfg, limit).await
+        }
+        None => Ok(()),
+    }
 }
@@ -1,6 +1,14 @@
 //! Trait to implement custom Health checks
+mod cached;
+mod grpc;
+mod registry;
+
+pub use cached::*;
+pub use grpc::*;
+pub use registry::*;
+
 use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::response::{IntoResponse, Response};
 
 /// Trait to implement custom health check which will be used to respond to health check requests
 #[axum::async_trait]
@@ -17,6 +25,13 @@ pub trait HealthExt: Send + Sync + 'static + Clone {
     /// returns [`Self::ReadyResponse`] in response to configured endpoint. By default `/ready`.
     /// For more information see [`crate::configuration::ManagementConfig`]
     async fn ready(&self) -> Self::ReadyResponse;
+
+    /// returns response to startup probe on configured endpoint. By default `/startup`.
+    /// Responds with `OK` unless overridden, startup is additionally gated by [`crate::Application::warm_up`].
+    /// For more information see [`crate::configuration::ManagementConfig`]
+    async fn started(&self) -> Response {
+        (StatusCode::OK, "OK").into_response()
+    }
 }
 
 /// Default structure to mark application always alive and ready.
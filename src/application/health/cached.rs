@@ -0,0 +1,380 @@
+use crate::application::health::HealthExt;
+use axum::body::{boxed, Bytes, Full};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, timeout, MissedTickBehavior};
+use tracing::warn;
+
+/// Lower bound for `refresh_interval`, as zero interval is not allowed by [`tokio::time::interval`].
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Clone)]
+struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl CachedResponse {
+    async fn from_response(response: Response) -> Self {
+        let (parts, body) = response.into_parts();
+        let body = hyper::body::to_bytes(body).await.unwrap_or_else(|err| {
+            warn!("Failed to read health check response body: `{err}`.");
+            Bytes::new()
+        });
+
+        Self {
+            status: parts.status,
+            headers: parts.headers,
+            body,
+        }
+    }
+
+    fn to_response(&self) -> Response {
+        let mut response = Response::new(boxed(Full::new(self.body.clone())));
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+        response
+    }
+}
+
+#[derive(Default)]
+struct State {
+    alive: Option<CachedResponse>,
+    ready: Option<CachedResponse>,
+    /// last refresh of ready check.
+    refreshed_at: Option<Instant>,
+}
+
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Wraps any [`HealthExt`] to run its checks in background every `refresh_interval` and respond with the last result.\
+/// Alive and ready checks are refreshed independently, each check not finished within `stale_after` is cancelled
+/// and cached as [`503 Service Unavailable`](StatusCode::SERVICE_UNAVAILABLE) with `TIMEOUT` body.
+/// If ready check was not refreshed for longer than `stale_after` since last refresh or creation,
+/// ready endpoint responds with [`503 Service Unavailable`](StatusCode::SERVICE_UNAVAILABLE).
+/// Until first refresh completes ready endpoint responds with [`503 Service Unavailable`](StatusCode::SERVICE_UNAVAILABLE)
+/// and alive endpoint calls wrapped [`HealthExt`] directly. Startup endpoint always calls wrapped [`HealthExt`].\
+/// `refresh_interval` is clamped to at least 1 millisecond.\
+/// Background refresh is spawned on creation, so it must be created within tokio runtime, and is stopped once all clones are dropped.
+///
+/// Example:
+/// ```no_run
+/// use fregate::health::{CachedHealth, HealthRegistry};
+/// use fregate::{tokio, AppConfig, Application};
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let registry = HealthRegistry::default().register("database", || async { Ok::<_, String>(()) });
+///     let health = CachedHealth::new(registry, Duration::from_secs(5), Duration::from_secs(30));
+///
+///     Application::new(AppConfig::default())
+///         .health_indicator(health)
+///         .serve()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CachedHealth<H> {
+    health: H,
+    stale_after: Duration,
+    created_at: Instant,
+    state: Arc<Mutex<State>>,
+    _refresh: Arc<[AbortOnDrop; 2]>,
+}
+
+impl<H> Debug for CachedHealth<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedHealth")
+            .field("stale_after", &self.stale_after)
+            .field("refreshed_at", &lock(&self.state).refreshed_at)
+            .finish()
+    }
+}
+
+impl<H: HealthExt> CachedHealth<H> {
+    /// Spawns background refresh of `health` checks every `refresh_interval`.
+    pub fn new(health: H, refresh_interval: Duration, stale_after: Duration) -> Self {
+        let state = Arc::new(Mutex::new(State::default()));
+        let refresh_interval = refresh_interval.max(MIN_REFRESH_INTERVAL);
+
+        let alive = tokio::spawn({
+            let health = health.clone();
+            let state = state.clone();
+
+            refresh(
+                refresh_interval,
+                stale_after,
+                move || {
+                    let health = health.clone();
+                    async move { health.alive().await.into_response() }
+                },
+                move |alive| lock(&state).alive = Some(alive),
+            )
+        });
+
+        let ready = tokio::spawn({
+            let health = health.clone();
+            let state = state.clone();
+
+            refresh(
+                refresh_interval,
+                stale_after,
+                move || {
+                    let health = health.clone();
+                    async move { health.ready().await.into_response() }
+                },
+                move |ready| {
+                    let mut state = lock(&state);
+                    state.ready = Some(ready);
+                    state.refreshed_at = Some(Instant::now());
+                },
+            )
+        });
+
+        Self {
+            health,
+            stale_after,
+            created_at: Instant::now(),
+            state,
+            _refresh: Arc::new([AbortOnDrop(alive), AbortOnDrop(ready)]),
+        }
+    }
+
+    fn is_stale(&self, refreshed_at: Instant) -> bool {
+        refreshed_at.elapsed() > self.stale_after
+    }
+}
+
+#[axum::async_trait]
+impl<H: HealthExt> HealthExt for CachedHealth<H> {
+    type HealthResponse = Response;
+    type ReadyResponse = Response;
+
+    async fn alive(&self) -> Self::HealthResponse {
+        let cached = lock(&self.state).alive.clone();
+
+        match cached {
+            Some(cached) => cached.to_response(),
+            None => self.health.alive().await.into_response(),
+        }
+    }
+
+    async fn ready(&self) -> Self::ReadyResponse {
+        let (cached, refreshed_at) = {
+            let state = lock(&self.state);
+            (state.ready.clone(), state.refreshed_at)
+        };
+
+        if self.is_stale(refreshed_at.unwrap_or(self.created_at)) {
+            return (StatusCode::SERVICE_UNAVAILABLE, "STALE").into_response();
+        }
+
+        match cached {
+            Some(cached) => cached.to_response(),
+            None => (StatusCode::SERVICE_UNAVAILABLE, "NOT READY").into_response(),
+        }
+    }
+
+    async fn started(&self) -> Response {
+        self.health.started().await
+    }
+}
+
+/// Runs `check` every `refresh_interval` and passes its response to `store`, cancelling `check` after `check_timeout`.
+async fn refresh<F, Fut>(
+    refresh_interval: Duration,
+    check_timeout: Duration,
+    check: F,
+    store: impl Fn(CachedResponse),
+) where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Response>,
+{
+    let mut ticker = interval(refresh_interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        let response = match timeout(check_timeout, check()).await {
+            Ok(response) => response,
+            Err(_elapsed) => {
+                warn!("Health check did not finish within `{check_timeout:?}`.");
+                (StatusCode::SERVICE_UNAVAILABLE, "TIMEOUT").into_response()
+            }
+        };
+        store(CachedResponse::from_response(response).await);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod cached_test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone, Default)]
+    struct Counting {
+        calls: Arc<AtomicUsize>,
+        stall_after: Option<usize>,
+        stall_alive: bool,
+    }
+
+    #[axum::async_trait]
+    impl HealthExt for Counting {
+        type HealthResponse = StatusCode;
+        type ReadyResponse = StatusCode;
+
+        async fn alive(&self) -> Self::HealthResponse {
+            if self.stall_alive {
+                std::future::pending::<()>().await;
+            }
+            StatusCode::OK
+        }
+
+        async fn ready(&self) -> Self::ReadyResponse {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst);
+            if self
+                .stall_after
+                .is_some_and(|stall_after| calls >= stall_after)
+            {
+                std::future::pending::<()>().await;
+            }
+            StatusCode::OK
+        }
+
+        async fn started(&self) -> Response {
+            (StatusCode::SERVICE_UNAVAILABLE, "STARTING").into_response()
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_cached_result() {
+        let counting = Counting::default();
+        let health = CachedHealth::new(
+            counting.clone(),
+            Duration::from_secs(60),
+            Duration::from_secs(60),
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        for _ in 0..5 {
+            assert_eq!(health.ready().await.status(), StatusCode::OK);
+        }
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn stale_result_is_not_ready() {
+        let counting = Counting {
+            stall_after: Some(1),
+            ..Counting::default()
+        };
+        let health = CachedHealth::new(
+            counting,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(health.ready().await.status(), StatusCode::OK);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(
+            health.ready().await.status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(health.alive().await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn not_ready_until_first_refresh() {
+        let counting = Counting {
+            stall_after: Some(0),
+            ..Counting::default()
+        };
+        let health = CachedHealth::new(
+            counting.clone(),
+            Duration::from_secs(60),
+            Duration::from_millis(50),
+        );
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let response = health.ready().await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(body, "NOT READY");
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let body = hyper::body::to_bytes(health.ready().await.into_body())
+            .await
+            .unwrap();
+        assert_eq!(body, "STALE");
+        assert_eq!(counting.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn stalled_alive_does_not_block_ready() {
+        let counting = Counting {
+            stall_alive: true,
+            ..Counting::default()
+        };
+        let health = CachedHealth::new(
+            counting.clone(),
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        );
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(health.ready().await.status(), StatusCode::OK);
+        assert!(counting.calls.load(Ordering::SeqCst) > 1);
+
+        let response = health.alive().await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(body, "TIMEOUT");
+    }
+
+    #[tokio::test]
+    async fn zero_refresh_interval_is_clamped() {
+        let health =
+            CachedHealth::new(Counting::default(), Duration::ZERO, Duration::from_secs(60));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert_eq!(health.ready().await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn forwards_started() {
+        let health = CachedHealth::new(
+            Counting::default(),
+            Duration::from_secs(60),
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(
+            health.started().await.status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
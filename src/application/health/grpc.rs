@@ -0,0 +1,381 @@
+use crate::application::health::HealthExt;
+use axum::response::IntoResponse;
+use hyper::Body;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::codegen::futures_core::Stream;
+use tonic::server::{Grpc, NamedService, ServerStreamingService, UnaryService};
+use tonic::{Request, Response, Status};
+use tower::Service;
+
+const CHECK_PATH: &str = "/grpc.health.v1.Health/Check";
+const WATCH_PATH: &str = "/grpc.health.v1.Health/Watch";
+const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+type StatusFuture = Pin<Box<dyn Future<Output = ServingStatus> + Send + 'static>>;
+type StatusFn = Arc<dyn Fn() -> StatusFuture + Send + Sync + 'static>;
+type ResponseFuture =
+    Pin<Box<dyn Future<Output = Result<hyper::Response<BoxBody>, Infallible>> + Send>>;
+
+/// `grpc.health.v1.HealthCheckRequest` message.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HealthCheckRequest {
+    /// service name, empty string stands for the whole server.
+    #[prost(string, tag = "1")]
+    pub service: String,
+}
+
+/// `grpc.health.v1.HealthCheckResponse` message.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HealthCheckResponse {
+    /// [`ServingStatus`] of requested service.
+    #[prost(enumeration = "ServingStatus", tag = "1")]
+    pub status: i32,
+}
+
+/// `grpc.health.v1.HealthCheckResponse.ServingStatus` enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum ServingStatus {
+    /// status is unknown.
+    Unknown = 0,
+    /// service is ready to serve requests.
+    Serving = 1,
+    /// service is not ready.
+    NotServing = 2,
+    /// requested service is not registered, used only by `Watch`.
+    ServiceUnknown = 3,
+}
+
+/// Implementation of [gRPC health checking protocol](https://github.com/grpc/grpc/blob/master/doc/health-checking.md)
+/// answering `grpc.health.v1.Health/Check` and `grpc.health.v1.Health/Watch` from [`HealthExt::ready`].\
+/// Service is [`ServingStatus::Serving`] if ready endpoint responds with success status code.
+/// Empty service name stands for the whole server, other names might be registered with [`GrpcHealthService::with_service`].\
+/// `Watch` polls status every [`GrpcHealthService::watch_interval`] and sends update only if status changed.\
+/// Use [`crate::Application::grpc_health_service`] to report status of the whole server same as ready endpoint,
+/// including warm-up and shutdown.
+///
+/// Example:
+/// ```no_run
+/// use fregate::axum::Router;
+/// use fregate::extensions::RouterTonicExt;
+/// use fregate::health::AlwaysReadyAndAlive;
+/// use fregate::{tokio, AppConfig, Application};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let health = AlwaysReadyAndAlive;
+///     let app = Application::new(AppConfig::default()).health_indicator(health);
+///     let grpc_health = app
+///         .grpc_health_service()
+///         .with_service("helloworld.Greeter", health);
+///
+///     app.router(Router::from_tonic_service(grpc_health))
+///         .serve()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct GrpcHealthService {
+    services: Arc<BTreeMap<String, StatusFn>>,
+    watch_interval: Duration,
+}
+
+impl Debug for GrpcHealthService {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrpcHealthService")
+            .field("services", &self.services.keys().collect::<Vec<_>>())
+            .field("watch_interval", &self.watch_interval)
+            .finish()
+    }
+}
+
+impl GrpcHealthService {
+    /// Creates service reporting status of the whole server from given [`HealthExt`].\
+    /// Given [`HealthExt`] is not aware of application warm-up and shutdown, see [`crate::Application::grpc_health_service`].
+    pub fn new<H: HealthExt>(health: H) -> Self {
+        Self {
+            services: Default::default(),
+            watch_interval: DEFAULT_WATCH_INTERVAL,
+        }
+        .with_service("", health)
+    }
+
+    /// Registers service name with its own [`HealthExt`].
+    #[must_use]
+    pub fn with_service<H: HealthExt>(mut self, name: &str, health: H) -> Self {
+        let status: StatusFn = Arc::new(move || {
+            let health = health.clone();
+            Box::pin(async move {
+                if health.ready().await.into_response().status().is_success() {
+                    ServingStatus::Serving
+                } else {
+                    ServingStatus::NotServing
+                }
+            })
+        });
+
+        Arc::make_mut(&mut self.services).insert(name.to_owned(), status);
+        self
+    }
+
+    /// Sets how often status is polled for `Watch` streams. By default `1s`.
+    #[must_use]
+    pub fn watch_interval(self, watch_interval: Duration) -> Self {
+        Self {
+            watch_interval,
+            ..self
+        }
+    }
+
+    async fn status(&self, service: &str) -> Option<ServingStatus> {
+        let status = self.services.get(service)?;
+        Some(status().await)
+    }
+
+    async fn check(&self, service: &str) -> Result<HealthCheckResponse, Status> {
+        let status = self
+            .status(service)
+            .await
+            .ok_or_else(|| Status::not_found(format!("Unknown service: `{service}`.")))?;
+
+        Ok(HealthCheckResponse {
+            status: status as i32,
+        })
+    }
+
+    fn watch(&self, service: String) -> WatchStream {
+        let (sender, receiver) = mpsc::channel(1);
+        let this = self.clone();
+
+        tokio::spawn(async move {
+            let mut last = None;
+
+            loop {
+                let status = this
+                    .status(&service)
+                    .await
+                    .unwrap_or(ServingStatus::ServiceUnknown);
+
+                if last != Some(status) {
+                    let response = HealthCheckResponse {
+                        status: status as i32,
+                    };
+                    if sender.send(Ok(response)).await.is_err() {
+                        return;
+                    }
+                    last = Some(status);
+                }
+
+                tokio::select! {
+                    _ = tokio::time::sleep(this.watch_interval) => {},
+                    _ = sender.closed() => return,
+                }
+            }
+        });
+
+        WatchStream(receiver)
+    }
+}
+
+/// Stream of `Watch` responses.
+struct WatchStream(mpsc::Receiver<Result<HealthCheckResponse, Status>>);
+
+impl Stream for WatchStream {
+    type Item = Result<HealthCheckResponse, Status>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_recv(cx)
+    }
+}
+
+struct CheckSvc(GrpcHealthService);
+
+impl UnaryService<HealthCheckRequest> for CheckSvc {
+    type Response = HealthCheckResponse;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<Self::Response>, Status>> + Send>>;
+
+    fn call(&mut self, request: Request<HealthCheckRequest>) -> Self::Future {
+        let health = self.0.clone();
+
+        Box::pin(async move {
+            health
+                .check(&request.into_inner().service)
+                .await
+                .map(Response::new)
+        })
+    }
+}
+
+struct WatchSvc(GrpcHealthService);
+
+impl ServerStreamingService<HealthCheckRequest> for WatchSvc {
+    type Response = HealthCheckResponse;
+    type ResponseStream = WatchStream;
+    type Future = std::future::Ready<Result<Response<Self::ResponseStream>, Status>>;
+
+    fn call(&mut self, request: Request<HealthCheckRequest>) -> Self::Future {
+        let stream = self.0.watch(request.into_inner().service);
+        std::future::ready(Ok(Response::new(stream)))
+    }
+}
+
+impl Service<hyper::Request<Body>> for GrpcHealthService {
+    type Response = hyper::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: hyper::Request<Body>) -> Self::Future {
+        let health = self.clone();
+
+        match request.uri().path() {
+            CHECK_PATH => Box::pin(async move {
+                let mut grpc = Grpc::new(ProstCodec::default());
+                Ok(grpc.unary(CheckSvc(health), request).await)
+            }),
+            WATCH_PATH => Box::pin(async move {
+                let mut grpc = Grpc::new(ProstCodec::default());
+                Ok(grpc.server_streaming(WatchSvc(health), request).await)
+            }),
+            _ => Box::pin(async { Ok(Status::unimplemented("Unknown method.").to_http()) }),
+        }
+    }
+}
+
+impl NamedService for GrpcHealthService {
+    const NAME: &'static str = "grpc.health.v1.Health";
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod grpc_test {
+    use super::*;
+    use crate::extensions::RouterTonicExt;
+    use crate::health::AlwaysReadyAndAlive;
+    use axum::http::StatusCode;
+    use axum::Router;
+    use prost::Message;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use tower::ServiceExt;
+
+    #[derive(Clone, Default)]
+    struct Toggle(Arc<AtomicBool>);
+
+    #[axum::async_trait]
+    impl HealthExt for Toggle {
+        type HealthResponse = StatusCode;
+        type ReadyResponse = StatusCode;
+
+        async fn alive(&self) -> Self::HealthResponse {
+            StatusCode::OK
+        }
+
+        async fn ready(&self) -> Self::ReadyResponse {
+            if self.0.load(Ordering::SeqCst) {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+        }
+    }
+
+    fn frame(message: &impl Message) -> Vec<u8> {
+        let message = message.encode_to_vec();
+        let mut frame = vec![0];
+        frame.extend_from_slice(&u32::try_from(message.len()).unwrap().to_be_bytes());
+        frame.extend_from_slice(&message);
+        frame
+    }
+
+    #[tokio::test]
+    async fn check_through_router() {
+        let router = Router::from_tonic_service(GrpcHealthService::new(AlwaysReadyAndAlive));
+        let request = hyper::Request::post(CHECK_PATH)
+            .header("content-type", "application/grpc")
+            .body(Body::from(frame(&HealthCheckRequest::default())))
+            .unwrap();
+
+        let response = router.oneshot(request).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let response = HealthCheckResponse::decode(body.get(5..).unwrap()).unwrap();
+
+        assert_eq!(response.status, ServingStatus::Serving as i32);
+    }
+
+    #[tokio::test]
+    async fn check_per_service() {
+        let toggle = Toggle::default();
+        let health = GrpcHealthService::new(AlwaysReadyAndAlive).with_service("db", toggle);
+
+        let db = health.check("db").await.unwrap();
+        assert_eq!(db.status, ServingStatus::NotServing as i32);
+
+        let unknown = health.check("unknown").await.unwrap_err();
+        assert_eq!(unknown.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn watch_sends_changes() {
+        let toggle = Toggle::default();
+        let health =
+            GrpcHealthService::new(toggle.clone()).watch_interval(Duration::from_millis(10));
+
+        let mut stream = health.watch(String::new());
+        let first = stream.0.recv().await.unwrap().unwrap();
+        assert_eq!(first.status, ServingStatus::NotServing as i32);
+
+        toggle.0.store(true, Ordering::SeqCst);
+        let second = stream.0.recv().await.unwrap().unwrap();
+        assert_eq!(second.status, ServingStatus::Serving as i32);
+
+        let mut unknown = health.watch("unknown".to_owned());
+        let status = unknown.0.recv().await.unwrap().unwrap();
+        assert_eq!(status.status, ServingStatus::ServiceUnknown as i32);
+    }
+
+    #[tokio::test]
+    async fn application_health_reports_warm_up_and_shutdown() {
+        let config = crate::AppConfig {
+            port: 0,
+            ..Default::default()
+        };
+        let (warmed_up, warm_up) = tokio::sync::oneshot::channel::<()>();
+
+        let app = crate::Application::new(config);
+        let health = app.grpc_health_service();
+        let running = app
+            .warm_up(async move {
+                let _ = warm_up.await;
+            })
+            .bind()
+            .await
+            .unwrap();
+
+        let status = health.check("").await.unwrap();
+        assert_eq!(status.status, ServingStatus::NotServing as i32);
+
+        warmed_up.send(()).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let status = health.check("").await.unwrap();
+        assert_eq!(status.status, ServingStatus::Serving as i32);
+
+        running.shutdown();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let status = health.check("").await.unwrap();
+        assert_eq!(status.status, ServingStatus::NotServing as i32);
+    }
+}
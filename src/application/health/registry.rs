@@ -0,0 +1,307 @@
+use crate::application::health::HealthExt;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::time::timeout;
+
+type CheckFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'static>>;
+type CheckFn = Arc<dyn Fn() -> CheckFuture + Send + Sync + 'static>;
+
+/// Options of single check registered in [`HealthRegistry`].
+#[derive(Debug, Clone, Copy)]
+pub struct HealthCheckOptions {
+    /// check is reported as failed if it does not complete within timeout.
+    pub timeout: Duration,
+    /// if critical check fails application is not ready, otherwise it is reported as degraded.
+    pub critical: bool,
+}
+
+impl Default for HealthCheckOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(1),
+            critical: true,
+        }
+    }
+}
+
+/// Status of single component or of application in total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// everything works.
+    Up,
+    /// non-critical components failed, application is still ready.
+    Degraded,
+    /// critical components failed, application is not ready.
+    Down,
+}
+
+/// Result of single registered check.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    /// [`HealthStatus::Up`] or [`HealthStatus::Down`].
+    pub status: HealthStatus,
+    /// whether check is critical.
+    pub critical: bool,
+    /// time spent on check in milliseconds.
+    pub latency_ms: u128,
+    /// error returned by check or timeout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregated result of all checks registered in [`HealthRegistry`].\
+/// Responds with [`200 OK`](StatusCode::OK) unless status is [`HealthStatus::Down`], in that case with [`503 Service Unavailable`](StatusCode::SERVICE_UNAVAILABLE).
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    /// aggregated status.
+    pub status: HealthStatus,
+    /// result of each check by its name.
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+impl HealthReport {
+    /// Returns `true` unless any critical check failed.
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Down
+    }
+}
+
+impl IntoResponse for HealthReport {
+    fn into_response(self) -> Response {
+        let status = if self.is_ready() {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+
+        (status, Json(self)).into_response()
+    }
+}
+
+#[derive(Clone)]
+struct Check {
+    name: String,
+    options: HealthCheckOptions,
+    check: CheckFn,
+}
+
+/// [`HealthExt`] which runs named checks of application dependencies concurrently and reports their aggregated status on ready endpoint.\
+/// Alive endpoint always responds with `OK`, as failing dependency is not a reason to restart application.
+///
+/// Example:
+/// ```no_run
+/// use fregate::health::{HealthCheckOptions, HealthRegistry};
+/// use fregate::{tokio, AppConfig, Application};
+/// use std::time::Duration;
+///
+/// async fn ping_database() -> Result<(), String> {
+///     Ok(())
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let health = HealthRegistry::default()
+///         .register("database", ping_database)
+///         .register_with(
+///             "cache",
+///             HealthCheckOptions {
+///                 timeout: Duration::from_millis(200),
+///                 critical: false,
+///             },
+///             || async { Err::<(), _>("cache is not available") },
+///         );
+///
+///     Application::new(AppConfig::default())
+///         .health_indicator(health)
+///         .serve()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct HealthRegistry {
+    checks: Vec<Check>,
+}
+
+impl Debug for HealthRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.checks.iter().map(|check| &check.name))
+            .finish()
+    }
+}
+
+impl HealthRegistry {
+    /// Registers critical check with default [`HealthCheckOptions`].
+    #[must_use]
+    pub fn register<F, Fut, E>(self, name: &str, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        self.register_with(name, HealthCheckOptions::default(), check)
+    }
+
+    /// Registers check with given [`HealthCheckOptions`].
+    #[must_use]
+    pub fn register_with<F, Fut, E>(
+        mut self,
+        name: &str,
+        options: HealthCheckOptions,
+        check: F,
+    ) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        let check: CheckFn = Arc::new(move || {
+            let fut = check();
+            Box::pin(async move { fut.await.map_err(|err| err.to_string()) })
+        });
+
+        self.checks.push(Check {
+            name: name.to_owned(),
+            options,
+            check,
+        });
+        self
+    }
+
+    /// Runs all registered checks concurrently and aggregates their results.
+    pub async fn check(&self) -> HealthReport {
+        let handles = self
+            .checks
+            .iter()
+            .map(|check| {
+                let Check {
+                    name,
+                    options,
+                    check,
+                } = check.clone();
+
+                let handle = tokio::spawn(async move {
+                    let started = Instant::now();
+                    let error = match timeout(options.timeout, check()).await {
+                        Ok(Ok(())) => None,
+                        Ok(Err(err)) => Some(err),
+                        Err(_elapsed) => Some(format!("timed out after `{:?}`", options.timeout)),
+                    };
+
+                    (started.elapsed(), error)
+                });
+
+                (name, options, handle)
+            })
+            .collect::<Vec<_>>();
+
+        let mut status = HealthStatus::Up;
+        let mut components = BTreeMap::new();
+
+        for (name, options, handle) in handles {
+            let (latency, error) = match handle.await {
+                Ok(outcome) => outcome,
+                Err(err) => (Duration::ZERO, Some(format!("check panicked: `{err}`"))),
+            };
+
+            if error.is_some() {
+                status = match (status, options.critical) {
+                    (_, true) | (HealthStatus::Down, false) => HealthStatus::Down,
+                    (_, false) => HealthStatus::Degraded,
+                };
+            }
+
+            components.insert(
+                name,
+                ComponentHealth {
+                    status: if error.is_some() {
+                        HealthStatus::Down
+                    } else {
+                        HealthStatus::Up
+                    },
+                    critical: options.critical,
+                    latency_ms: latency.as_millis(),
+                    error,
+                },
+            );
+        }
+
+        HealthReport { status, components }
+    }
+}
+
+#[axum::async_trait]
+impl HealthExt for HealthRegistry {
+    type HealthResponse = (StatusCode, &'static str);
+    type ReadyResponse = HealthReport;
+
+    async fn alive(&self) -> Self::HealthResponse {
+        (StatusCode::OK, "OK")
+    }
+
+    async fn ready(&self) -> Self::ReadyResponse {
+        self.check().await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod registry_test {
+    use super::*;
+
+    #[tokio::test]
+    async fn aggregates_checks() {
+        let registry = HealthRegistry::default()
+            .register("database", || async { Ok::<_, String>(()) })
+            .register_with(
+                "cache",
+                HealthCheckOptions {
+                    timeout: Duration::from_secs(1),
+                    critical: false,
+                },
+                || async { Err::<(), _>("connection refused") },
+            );
+
+        let report = registry.check().await;
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(report.is_ready());
+
+        let cache = report.components.get("cache").unwrap();
+        assert_eq!(cache.status, HealthStatus::Down);
+        assert_eq!(cache.error.as_deref(), Some("connection refused"));
+
+        let response = registry.ready().await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn critical_check_timeout_is_not_ready() {
+        let registry = HealthRegistry::default().register_with(
+            "downstream",
+            HealthCheckOptions {
+                timeout: Duration::from_millis(10),
+                critical: true,
+            },
+            || async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                Ok::<_, String>(())
+            },
+        );
+
+        let report = registry.check().await;
+        assert_eq!(report.status, HealthStatus::Down);
+
+        let response = report.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
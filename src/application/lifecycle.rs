@@ -0,0 +1,199 @@
+use crate::application::shutdown::{shutdown_signal, Shutdown};
+use crate::application::supervisor::Supervisor;
+use crate::error::Result;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+pub(crate) type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+pub(crate) type Hook = Box<dyn FnOnce() -> BoxFuture + Send + 'static>;
+
+/// Startup gate of [`crate::Application`], opened once all warm-up futures finish.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Startup {
+    started: Arc<AtomicBool>,
+}
+
+impl Startup {
+    pub(crate) fn is_started(&self) -> bool {
+        self.started.load(Ordering::Acquire)
+    }
+
+    fn mark_started(&self) {
+        self.started.store(true, Ordering::Release);
+    }
+}
+
+/// User supplied shutdown future and hooks executed around [`crate::Application`] serving.
+#[derive(Default)]
+pub(crate) struct Lifecycle {
+    trigger: CancellationToken,
+    startup: Startup,
+    shutdown: Option<BoxFuture>,
+    on_start: Vec<Hook>,
+    on_shutdown: Vec<Hook>,
+    warm_up: Vec<BoxFuture>,
+}
+
+impl Debug for Lifecycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            trigger,
+            startup,
+            shutdown,
+            on_start,
+            on_shutdown,
+            warm_up,
+        } = self;
+
+        f.debug_struct("Lifecycle")
+            .field("triggered", &trigger.is_cancelled())
+            .field("started", &startup.is_started())
+            .field(
+                "shutdown",
+                if shutdown.is_some() { &"Some" } else { &"None" },
+            )
+            .field("on_start", &on_start.len())
+            .field("on_shutdown", &on_shutdown.len())
+            .field("warm_up", &warm_up.len())
+            .finish()
+    }
+}
+
+impl Lifecycle {
+    /// Returns token which starts shutdown once cancelled.
+    pub(crate) fn trigger(&self) -> CancellationToken {
+        self.trigger.clone()
+    }
+
+    /// Returns startup gate which is opened once all warm-up futures finish.
+    /// Gate is already open if no warm-up futures were registered.
+    pub(crate) fn startup(&self) -> Startup {
+        if self.warm_up.is_empty() {
+            self.startup.mark_started();
+        }
+
+        self.startup.clone()
+    }
+
+    /// Returns startup gate without opening it, it is opened by [`Lifecycle::startup`] or once warm-up futures finish.
+    pub(crate) fn startup_gate(&self) -> Startup {
+        self.startup.clone()
+    }
+
+    pub(crate) fn push_warm_up(&mut self, warm_up: impl Future<Output = ()> + Send + 'static) {
+        self.warm_up.push(Box::pin(warm_up));
+    }
+
+    pub(crate) fn set_shutdown(&mut self, shutdown: impl Future<Output = ()> + Send + 'static) {
+        self.shutdown = Some(Box::pin(shutdown));
+    }
+
+    pub(crate) fn push_on_start<F, Fut>(&mut self, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.on_start.push(into_hook(hook));
+    }
+
+    pub(crate) fn push_on_shutdown<F, Fut>(&mut self, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.on_shutdown.push(into_hook(hook));
+    }
+
+    /// Starts background tasks and runs `on_start` hooks, serves until shutdown is triggered
+    /// while warm-up futures are running, then stops background tasks and runs `on_shutdown` hooks.\
+    /// Expects all listeners to be already bound.
+    pub(crate) async fn run(
+        self,
+        shutdown: Shutdown,
+        supervisor: Supervisor,
+        servers: impl Future<Output = Result<()>>,
+    ) -> Result<()> {
+        let Self {
+            trigger,
+            startup,
+            shutdown: custom_shutdown,
+            on_start,
+            on_shutdown,
+            warm_up,
+        } = self;
+
+        supervisor.start();
+
+        for hook in on_start {
+            hook().await;
+        }
+
+        let warm_up = tokio::spawn(async move {
+            if warm_up.is_empty() {
+                startup.mark_started();
+                return;
+            }
+
+            let mut tasks = JoinSet::new();
+            for fut in warm_up {
+                tasks.spawn(fut);
+            }
+
+            let mut failed = false;
+
+            while let Some(result) = tasks.join_next().await {
+                if let Err(err) = result {
+                    error!("Warm-up failed: `{err}`.");
+                    failed = true;
+                }
+            }
+
+            if !failed {
+                startup.mark_started();
+                info!("Warm-up finished, application started.");
+            }
+        });
+
+        let cancel = supervisor.clone();
+        let signal = async move {
+            let custom_shutdown = async move {
+                match custom_shutdown {
+                    Some(custom_shutdown) => custom_shutdown.await,
+                    None => std::future::pending().await,
+                }
+            };
+
+            tokio::select! {
+                _ = shutdown_signal() => {},
+                _ = custom_shutdown => {},
+                _ = trigger.cancelled() => {},
+            }
+            cancel.cancel();
+        };
+
+        let drain_timeout = shutdown.drain_timeout();
+        let result = shutdown.run(servers, signal).await;
+        warm_up.abort();
+        supervisor.stop(drain_timeout).await;
+
+        for hook in on_shutdown {
+            hook().await;
+        }
+
+        result
+    }
+}
+
+fn into_hook<F, Fut>(hook: F) -> Hook
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    Box::new(move || Box::pin(hook()))
+}
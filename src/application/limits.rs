@@ -0,0 +1,261 @@
+//! Connection and in-flight request limits configured by [`LimitsConfig`].
+use crate::configuration::LimitsConfig;
+use crate::middleware::is_grpc;
+use axum::extract::connect_info::Connected;
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use hyper::server::accept::Accept;
+use hyper::server::conn::AddrStream;
+use metrics::{describe_counter, increment_counter};
+use pin_project_lite::pin_project;
+use std::io::IoSlice;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+const CONNECTIONS_REJECTED_METRIC: &str = "connections_rejected";
+const REQUESTS_REJECTED_METRIC: &str = "requests_rejected";
+
+/// Limits number of open connections, unlimited if created without maximum.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConnectionLimit {
+    semaphore: Option<Arc<Semaphore>>,
+}
+
+impl ConnectionLimit {
+    pub(crate) fn new(max_connections: Option<usize>) -> Self {
+        if max_connections.is_some() {
+            describe_counter!(
+                CONNECTIONS_REJECTED_METRIC,
+                "Number of connections closed because of max connections limit."
+            );
+        }
+
+        Self {
+            semaphore: max_connections.map(|max| Arc::new(Semaphore::new(max))),
+        }
+    }
+
+    /// Wraps connection to hold connection slot until it is closed.
+    /// Returns [`None`] and increments rejection metric if limit is reached.
+    pub(crate) fn try_acquire<C>(&self, conn: C) -> Option<LimitedConn<C>> {
+        let permit = match &self.semaphore {
+            Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    increment_counter!(CONNECTIONS_REJECTED_METRIC);
+                    return None;
+                }
+            },
+            None => None,
+        };
+
+        Some(LimitedConn {
+            inner: conn,
+            _permit: permit,
+        })
+    }
+}
+
+pin_project! {
+    /// Connection holding a slot of [`ConnectionLimit`] until it is dropped.
+    #[derive(Debug)]
+    pub(crate) struct LimitedConn<C> {
+        #[pin]
+        inner: C,
+        _permit: Option<OwnedSemaphorePermit>,
+    }
+}
+
+impl<C> LimitedConn<C> {
+    pub(crate) fn get_ref(&self) -> &C {
+        &self.inner
+    }
+
+    /// Replaces wrapped connection keeping its slot, e.g. with result of TLS handshake.
+    #[cfg(feature = "tls")]
+    pub(crate) async fn upgrade<D, E, Fut>(
+        self,
+        f: impl FnOnce(C) -> Fut,
+    ) -> Result<LimitedConn<D>, E>
+    where
+        Fut: std::future::Future<Output = Result<D, E>>,
+    {
+        let Self { inner, _permit } = self;
+
+        Ok(LimitedConn {
+            inner: f(inner).await?,
+            _permit,
+        })
+    }
+}
+
+impl<C: AsyncRead> AsyncRead for LimitedConn<C> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        self.project().inner.poll_read(cx, buf)
+    }
+}
+
+impl<C: AsyncWrite> AsyncWrite for LimitedConn<C> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.project().inner.poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        self.project().inner.poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+impl Connected<&LimitedConn<AddrStream>> for SocketAddr {
+    fn connect_info(target: &LimitedConn<AddrStream>) -> Self {
+        target.get_ref().remote_addr()
+    }
+}
+
+/// [`Accept`] which closes connections accepted above [`ConnectionLimit`].
+pub(crate) struct LimitedIncoming<I> {
+    inner: I,
+    limit: ConnectionLimit,
+}
+
+impl<I> LimitedIncoming<I> {
+    pub(crate) fn new(inner: I, limit: ConnectionLimit) -> Self {
+        Self { inner, limit }
+    }
+}
+
+impl<I: Accept + Unpin> Accept for LimitedIncoming<I> {
+    type Conn = LimitedConn<I::Conn>;
+    type Error = I::Error;
+
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+        let this = self.get_mut();
+
+        loop {
+            match Pin::new(&mut this.inner).poll_accept(cx) {
+                Poll::Ready(Some(Ok(conn))) => {
+                    if let Some(conn) = this.limit.try_acquire(conn) {
+                        return Poll::Ready(Some(Ok(conn)));
+                    }
+                }
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Limits number of requests processed at the same time.
+#[derive(Debug, Clone)]
+pub(crate) struct InFlightLimit {
+    semaphore: Arc<Semaphore>,
+}
+
+impl InFlightLimit {
+    pub(crate) fn new(limits_cfg: &LimitsConfig) -> Option<Self> {
+        let max = limits_cfg.max_in_flight_requests?;
+        describe_counter!(
+            REQUESTS_REJECTED_METRIC,
+            "Number of requests rejected because of max in-flight requests limit."
+        );
+
+        Some(Self {
+            semaphore: Arc::new(Semaphore::new(max)),
+        })
+    }
+
+    /// Middleware rejecting requests above the limit with `503 Service Unavailable` or gRPC `ResourceExhausted`.
+    pub(crate) async fn limit<B>(self, req: Request<B>, next: Next<B>) -> Response {
+        let Ok(_permit) = self.semaphore.try_acquire_owned() else {
+            increment_counter!(REQUESTS_REJECTED_METRIC);
+
+            return if is_grpc(req.headers()) {
+                tonic::Status::resource_exhausted("Too many in-flight requests.")
+                    .to_http()
+                    .map(axum::body::boxed)
+            } else {
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Too many in-flight requests.",
+                )
+                    .into_response()
+            };
+        };
+
+        next.run(req).await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod limits_test {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::future::poll_fn;
+
+    struct Conns(VecDeque<u32>);
+
+    impl Accept for Conns {
+        type Conn = u32;
+        type Error = std::io::Error;
+
+        fn poll_accept(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Conn, Self::Error>>> {
+            Poll::Ready(self.get_mut().0.pop_front().map(Ok))
+        }
+    }
+
+    #[tokio::test]
+    async fn closes_connections_above_limit() {
+        let limit = ConnectionLimit::new(Some(1));
+        let conns = Conns(VecDeque::from([1, 2, 3]));
+        let mut incoming = LimitedIncoming::new(conns, limit.clone());
+
+        let first = poll_fn(|cx| Pin::new(&mut incoming).poll_accept(cx))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(*first.get_ref(), 1);
+
+        // 2 and 3 are dropped while first connection is open.
+        let rest = poll_fn(|cx| Pin::new(&mut incoming).poll_accept(cx)).await;
+        assert!(rest.is_none());
+
+        drop(first);
+        assert!(limit.try_acquire(4).is_some());
+    }
+}
@@ -0,0 +1,275 @@
+//! Listeners [`crate::Application`] might accept connections on instead of binding to configured host and port.
+use crate::error::Result;
+use hyper::server::conn::AddrIncoming;
+use std::fmt::{Display, Formatter};
+use std::net::{SocketAddr, TcpListener};
+#[cfg(unix)]
+use {
+    hyper::server::accept::Accept,
+    std::future::Future,
+    std::io,
+    std::os::unix::net::UnixListener,
+    std::path::{Path, PathBuf},
+    std::pin::Pin,
+    std::task::{ready, Context, Poll},
+    std::time::Duration,
+    tokio::time::Sleep,
+    tracing::{debug, error},
+};
+
+/// Pre-opened listener to be used by [`crate::Application`] instead of binding to [`crate::AppConfig::host`] and [`crate::AppConfig::port`].\
+/// Example:
+/// ```no_run
+/// use fregate::listener::Listener;
+/// use fregate::{tokio, AppConfig, Application};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // bind to any free port and read it before serving.
+///     let tcp = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+///     let port = tcp.local_addr().unwrap().port();
+///     println!("Listening on port: {port}");
+///
+///     Application::new(AppConfig::default())
+///         .listener(Listener::from(tcp))
+///         .serve()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub enum Listener {
+    /// TCP listener.
+    Tcp(TcpListener),
+    /// Unix domain socket listener.
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl Listener {
+    /// Binds Unix domain socket by given path.
+    /// Stale socket file left by previous run is removed before binding.
+    #[cfg(unix)]
+    pub fn bind_unix(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+
+        match std::fs::remove_file(path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+
+        Ok(Self::Unix(UnixListener::bind(path)?))
+    }
+
+    /// Takes all listeners passed by systemd socket activation through `LISTEN_FDS` environment variable.\
+    /// Returns empty [`Vec`] if process was not started with socket activation.
+    #[cfg(feature = "systemd")]
+    pub fn from_systemd() -> Result<Vec<Self>> {
+        let mut listen_fd = listenfd::ListenFd::from_env();
+
+        (0..listen_fd.len())
+            .filter_map(|idx| match listen_fd.take_tcp_listener(idx) {
+                Ok(Some(listener)) => Some(Ok(Self::Tcp(listener))),
+                Ok(None) => None,
+                #[cfg(unix)]
+                Err(_) => listen_fd
+                    .take_unix_listener(idx)
+                    .map(|listener| listener.map(Self::Unix))
+                    .map_err(Into::into)
+                    .transpose(),
+                #[cfg(not(unix))]
+                Err(err) => Some(Err(err.into())),
+            })
+            .collect()
+    }
+
+    /// Returns address this listener is bound to.
+    pub fn local_addr(&self) -> Result<ListenerAddr> {
+        let addr = match self {
+            Self::Tcp(listener) => ListenerAddr::Tcp(listener.local_addr()?),
+            #[cfg(unix)]
+            Self::Unix(listener) => ListenerAddr::Unix(
+                listener
+                    .local_addr()?
+                    .as_pathname()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default(),
+            ),
+        };
+
+        Ok(addr)
+    }
+
+    pub(crate) fn into_incoming(self) -> Result<Incoming> {
+        let incoming = match self {
+            Self::Tcp(listener) => {
+                listener.set_nonblocking(true)?;
+                let listener = tokio::net::TcpListener::from_std(listener)?;
+                Incoming::Tcp(AddrIncoming::from_listener(listener)?)
+            }
+            #[cfg(unix)]
+            Self::Unix(listener) => {
+                listener.set_nonblocking(true)?;
+                Incoming::Unix(UnixIncoming::new(tokio::net::UnixListener::from_std(
+                    listener,
+                )?))
+            }
+        };
+
+        Ok(incoming)
+    }
+}
+
+impl From<TcpListener> for Listener {
+    fn from(listener: TcpListener) -> Self {
+        Self::Tcp(listener)
+    }
+}
+
+#[cfg(unix)]
+impl From<UnixListener> for Listener {
+    fn from(listener: UnixListener) -> Self {
+        Self::Unix(listener)
+    }
+}
+
+/// Address [`Listener`] is bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListenerAddr {
+    /// TCP socket address.
+    Tcp(SocketAddr),
+    /// Unix domain socket path.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl Display for ListenerAddr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// Bound listener ready to be served by hyper.
+pub(crate) enum Incoming {
+    Tcp(AddrIncoming),
+    #[cfg(unix)]
+    Unix(UnixIncoming),
+}
+
+impl Incoming {
+    pub(crate) fn local_addr(&self) -> ListenerAddr {
+        match self {
+            Self::Tcp(incoming) => ListenerAddr::Tcp(incoming.local_addr()),
+            #[cfg(unix)]
+            Self::Unix(incoming) => ListenerAddr::Unix(
+                incoming
+                    .listener
+                    .local_addr()
+                    .ok()
+                    .and_then(|addr| addr.as_pathname().map(Path::to_path_buf))
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+/// Pause before accepting again after an accept error which is neither per-connection nor fatal, e.g. `EMFILE`.
+#[cfg(unix)]
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_secs(1);
+
+#[cfg(unix)]
+pub(crate) struct UnixIncoming {
+    listener: tokio::net::UnixListener,
+    timeout: Option<Pin<Box<Sleep>>>,
+}
+
+#[cfg(unix)]
+impl UnixIncoming {
+    fn new(listener: tokio::net::UnixListener) -> Self {
+        Self {
+            listener,
+            timeout: None,
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Accept for UnixIncoming {
+    type Conn = tokio::net::UnixStream;
+    type Error = io::Error;
+
+    // Mirrors hyper's `AddrIncoming`: errors are returned to hyper only when the listener itself is unusable,
+    // as hyper stops the whole server on any returned error.
+    fn poll_accept(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<std::result::Result<Self::Conn, Self::Error>>> {
+        let this = self.get_mut();
+
+        if let Some(timeout) = &mut this.timeout {
+            ready!(timeout.as_mut().poll(cx));
+            this.timeout = None;
+        }
+
+        loop {
+            match ready!(this.listener.poll_accept(cx)) {
+                Ok((stream, _addr)) => return Poll::Ready(Some(Ok(stream))),
+                Err(err) if is_connection_error(&err) => {
+                    debug!("Unix accept error on dropped connection: {err}");
+                }
+                Err(err) if is_fatal_error(&err) => return Poll::Ready(Some(Err(err))),
+                Err(err) => {
+                    error!("Unix accept error: {err}, retrying in {ACCEPT_ERROR_BACKOFF:?}");
+                    let mut timeout = Box::pin(tokio::time::sleep(ACCEPT_ERROR_BACKOFF));
+                    if timeout.as_mut().poll(cx).is_pending() {
+                        this.timeout = Some(timeout);
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Errors affecting only the connection being accepted.
+#[cfg(unix)]
+fn is_connection_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::Interrupted
+    )
+}
+
+/// Errors meaning the listener can't accept anymore, e.g. `EINVAL` when socket is not listening.
+#[cfg(unix)]
+fn is_fatal_error(err: &io::Error) -> bool {
+    matches!(err.kind(), io::ErrorKind::InvalidInput)
+}
+
+#[cfg(all(test, unix))]
+mod listener_test {
+    use super::*;
+
+    #[test]
+    fn transient_accept_errors_are_not_fatal() {
+        let aborted = io::Error::from(io::ErrorKind::ConnectionAborted);
+        assert!(is_connection_error(&aborted));
+        assert!(!is_fatal_error(&aborted));
+
+        // EMFILE
+        let too_many_files = io::Error::from_raw_os_error(24);
+        assert!(!is_connection_error(&too_many_files));
+        assert!(!is_fatal_error(&too_many_files));
+
+        assert!(is_fatal_error(&io::Error::from(
+            io::ErrorKind::InvalidInput
+        )));
+    }
+}
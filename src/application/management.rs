@@ -1,8 +1,20 @@
+mod auth;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+mod log_level;
+#[cfg(feature = "profiling")]
+mod profiling;
+
 use crate::application::health::HealthExt;
+use crate::application::shutdown::Shutdown;
+use crate::application::supervisor::Supervisor;
 use crate::observability::render_metrics;
 use crate::version::VersionExt;
 use crate::{AppConfig, ManagementConfig};
-use axum::{routing::get, Extension, Router};
+use auth::ManagementAuth;
+use axum::middleware::from_fn;
+use axum::{routing::get, Extension, Json, Router};
+use log_level::{build_log_level_router, LogLevelState};
 use std::sync::Arc;
 
 pub(crate) fn build_management_router<T, H, V>(
@@ -10,19 +22,64 @@ pub(crate) fn build_management_router<T, H, V>(
     health_indicator: H,
     version: V,
     callback: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+    admin_routes: Router,
+    shutdown: Shutdown,
 ) -> Router
 where
     H: HealthExt,
     V: VersionExt<T>,
     T: Send + Sync + 'static,
 {
-    Router::new()
-        .merge(build_health_router(
-            &app_cfg.management_cfg,
-            health_indicator,
-        ))
-        .merge(build_metrics_router(&app_cfg.management_cfg, callback))
+    let management_cfg = &app_cfg.management_cfg;
+    let probes = build_health_router(management_cfg, health_indicator);
+    let admin = Router::new()
+        .merge(build_metrics_router(management_cfg, callback))
         .merge(build_version_router(app_cfg, version))
+        .merge(build_log_level_router(
+            management_cfg.endpoints.loglevel.as_ref(),
+            &management_cfg.loglevel,
+            LogLevelState::new(&app_cfg.observability_cfg, shutdown),
+        ))
+        .merge(build_config_router(app_cfg))
+        .merge(admin_routes);
+
+    let Some(auth) = ManagementAuth::new(&management_cfg.auth) else {
+        return probes.merge(admin);
+    };
+    let auth_layer = from_fn(move |req, next| auth.clone().check(req, next));
+
+    if management_cfg.auth.exempt_probes {
+        probes.merge(admin.layer(auth_layer))
+    } else {
+        probes.merge(admin).layer(auth_layer)
+    }
+}
+
+/// Routes added with cargo features, protected the same way as other management endpoints.
+#[allow(unused_mut, unused_variables)]
+pub(crate) fn build_feature_router(
+    management_cfg: &ManagementConfig,
+    supervisor: &Supervisor,
+) -> Router {
+    let mut router = Router::new();
+
+    #[cfg(feature = "diagnostics")]
+    {
+        router = router.merge(diagnostics::build_diagnostics_router(
+            management_cfg.endpoints.diagnostics.as_ref(),
+            supervisor.clone(),
+        ));
+    }
+
+    #[cfg(feature = "profiling")]
+    {
+        router = router.merge(profiling::build_profiling_router(
+            management_cfg.endpoints.profile.as_ref(),
+            management_cfg.endpoints.flamegraph.as_ref(),
+        ));
+    }
+
+    router
 }
 
 fn build_health_router<H: HealthExt>(
@@ -32,11 +89,16 @@ fn build_health_router<H: HealthExt>(
     // TODO: separate health and alive handlers
     let alive_handler = |health: Extension<H>| async move { health.alive().await };
     let ready_handler = |health: Extension<H>| async move { health.ready().await };
+    let startup_handler = |health: Extension<H>| async move { health.started().await };
 
     Router::new()
         .route(management_cfg.endpoints.health.as_ref(), get(alive_handler))
         .route(management_cfg.endpoints.live.as_ref(), get(alive_handler))
         .route(management_cfg.endpoints.ready.as_ref(), get(ready_handler))
+        .route(
+            management_cfg.endpoints.startup.as_ref(),
+            get(startup_handler),
+        )
         .layer(Extension(health_indicator))
 }
 
@@ -64,6 +126,22 @@ where
         .layer(Extension(version))
 }
 
+fn build_config_router<T>(app_cfg: &Arc<AppConfig<T>>) -> Router {
+    let config_dump = &app_cfg.management_cfg.config_dump;
+    if !config_dump.enabled {
+        return Router::new();
+    }
+
+    let effective_cfg = app_cfg.effective_cfg.clone();
+    let sanitize = config_dump.sanitize.clone();
+    let config_handler = move || std::future::ready(Json(effective_cfg.to_json(&sanitize)));
+
+    Router::new().route(
+        app_cfg.management_cfg.endpoints.config.as_ref(),
+        get(config_handler),
+    )
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod management_test {
@@ -121,7 +199,14 @@ mod management_test {
     async fn health_test() {
         let app_cfg = Arc::new(AppConfig::<Empty>::default());
 
-        let router = build_management_router(&app_cfg, CustomHealth, DefaultVersion, None);
+        let router = build_management_router(
+            &app_cfg,
+            CustomHealth,
+            DefaultVersion,
+            None,
+            Router::new(),
+            Shutdown::new(Default::default()),
+        );
         let request = Request::builder()
             .uri("http://0.0.0.0/health")
             .method("GET")
@@ -140,7 +225,14 @@ mod management_test {
     async fn live_test() {
         let app_cfg = Arc::new(AppConfig::<Empty>::default());
 
-        let router = build_management_router(&app_cfg, CustomHealth, DefaultVersion, None);
+        let router = build_management_router(
+            &app_cfg,
+            CustomHealth,
+            DefaultVersion,
+            None,
+            Router::new(),
+            Shutdown::new(Default::default()),
+        );
         let request = Request::builder()
             .uri("http://0.0.0.0/live")
             .method("GET")
@@ -155,11 +247,102 @@ mod management_test {
         assert_eq!(&body[..], b"OK");
     }
 
+    #[tokio::test]
+    async fn config_test() {
+        let mut app_cfg = AppConfig::<Empty>::default();
+        let disabled = build_management_router(
+            &Arc::new(app_cfg.clone()),
+            CustomHealth,
+            DefaultVersion,
+            None,
+            Router::new(),
+            Shutdown::new(Default::default()),
+        );
+        let request = || {
+            Request::builder()
+                .uri("http://0.0.0.0/config")
+                .method("GET")
+                .body(hyper::Body::empty())
+                .unwrap()
+        };
+
+        let response = disabled.oneshot(request()).await.unwrap();
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+        app_cfg.management_cfg.config_dump.enabled = true;
+        let router = build_management_router(
+            &Arc::new(app_cfg),
+            CustomHealth,
+            DefaultVersion,
+            None,
+            Router::new(),
+            Shutdown::new(Default::default()),
+        );
+
+        let response = router.oneshot(request()).await.unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(StatusCode::OK, status);
+        assert_eq!(
+            body.pointer("/port").unwrap(),
+            &serde_json::json!({"value": 8000, "source": "default"})
+        );
+    }
+
+    #[tokio::test]
+    async fn auth_test() {
+        let request = |uri: &str| {
+            Request::builder()
+                .uri(uri)
+                .method("GET")
+                .body(hyper::Body::empty())
+                .unwrap()
+        };
+
+        let mut app_cfg = AppConfig::<Empty>::default();
+        app_cfg.management_cfg.auth.token = Some("secret".to_owned());
+        let router = build_management_router(
+            &Arc::new(app_cfg.clone()),
+            CustomHealth,
+            DefaultVersion,
+            None,
+            Router::new(),
+            Shutdown::new(Default::default()),
+        );
+
+        let response = router.clone().oneshot(request("/health")).await.unwrap();
+        assert_eq!(StatusCode::OK, response.status());
+        let response = router.oneshot(request("/version")).await.unwrap();
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+
+        app_cfg.management_cfg.auth.exempt_probes = false;
+        let router = build_management_router(
+            &Arc::new(app_cfg),
+            CustomHealth,
+            DefaultVersion,
+            None,
+            Router::new(),
+            Shutdown::new(Default::default()),
+        );
+
+        let response = router.oneshot(request("/health")).await.unwrap();
+        assert_eq!(StatusCode::UNAUTHORIZED, response.status());
+    }
+
     #[tokio::test]
     async fn ready_test() {
         let app_cfg = Arc::new(AppConfig::default());
 
-        let router = build_management_router(&app_cfg, CustomHealth, DefaultVersion, None);
+        let router = build_management_router(
+            &app_cfg,
+            CustomHealth,
+            DefaultVersion,
+            None,
+            Router::new(),
+            Shutdown::new(Default::default()),
+        );
         let request = Request::builder()
             .uri("http://0.0.0.0/ready")
             .method("GET")
@@ -183,13 +366,24 @@ mod management_test {
         let app_cfg = Arc::new(AppConfig {
             host: IpAddr::V4(Ipv4Addr::LOCALHOST),
             port: 8000,
+            http_cfg: Default::default(),
+            limits_cfg: Default::default(),
             observability_cfg: Default::default(),
             management_cfg: Default::default(),
+            shutdown_cfg: Default::default(),
             private: Config::default(),
+            effective_cfg: Default::default(),
             worker_guard: None,
         });
 
-        let router = build_management_router(&app_cfg, CustomHealth, CustomVersion, None);
+        let router = build_management_router(
+            &app_cfg,
+            CustomHealth,
+            CustomVersion,
+            None,
+            Router::new(),
+            Shutdown::new(Default::default()),
+        );
         let request = Request::builder()
             .uri("http://0.0.0.0:8000/version")
             .method("GET")
@@ -0,0 +1,221 @@
+//! Access control for management endpoints configured by [`ManagementAuthConfig`].
+use crate::ManagementAuthConfig;
+use axum::extract::ConnectInfo;
+use axum::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ManagementAuth {
+    token: Option<Arc<str>>,
+    basic: Option<Arc<str>>,
+    credentials_required: bool,
+    allowed_ips: Option<Arc<[IpNet]>>,
+}
+
+impl ManagementAuth {
+    /// Returns [`None`] if access control is not configured.
+    pub(crate) fn new(auth_cfg: &ManagementAuthConfig) -> Option<Self> {
+        if !auth_cfg.is_enabled() {
+            return None;
+        }
+
+        let basic = auth_cfg
+            .basic_username
+            .as_ref()
+            .zip(auth_cfg.basic_password.as_ref())
+            .map(|(username, password)| {
+                Arc::from(STANDARD.encode(format!("{username}:{password}")))
+            });
+
+        Some(Self {
+            token: auth_cfg.token.as_deref().map(Arc::from),
+            basic,
+            // basic username without password rejects all requests instead of allowing empty password.
+            credentials_required: auth_cfg.token.is_some() || auth_cfg.basic_username.is_some(),
+            allowed_ips: auth_cfg.allowed_ips.as_deref().map(Arc::from),
+        })
+    }
+
+    /// Middleware rejecting requests from not allowed addresses with `403 Forbidden`
+    /// and requests without valid credentials with `401 Unauthorized`.
+    pub(crate) async fn check<B>(self, req: Request<B>, next: Next<B>) -> Response {
+        if let Some(allowed_ips) = &self.allowed_ips {
+            let allowed =
+                remote_ip(&req).is_some_and(|ip| allowed_ips.iter().any(|net| net.contains(&ip)));
+
+            if !allowed {
+                return (StatusCode::FORBIDDEN, "Forbidden.").into_response();
+            }
+        }
+
+        if !self.credentials_required {
+            return next.run(req).await;
+        }
+
+        let authorization = req
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split_once(' '));
+
+        let authorized = match authorization {
+            Some((scheme, credentials)) if scheme.eq_ignore_ascii_case("bearer") => self
+                .token
+                .as_deref()
+                .is_some_and(|token| constant_time_eq(token, credentials.trim())),
+            Some((scheme, credentials)) if scheme.eq_ignore_ascii_case("basic") => self
+                .basic
+                .as_deref()
+                .is_some_and(|basic| constant_time_eq(basic, credentials.trim())),
+            _ => false,
+        };
+
+        if authorized {
+            return next.run(req).await;
+        }
+
+        let challenge = if self.basic.is_some() {
+            "Basic realm=\"management\""
+        } else {
+            "Bearer"
+        };
+
+        (
+            StatusCode::UNAUTHORIZED,
+            [(WWW_AUTHENTICATE, challenge)],
+            "Unauthorized.",
+        )
+            .into_response()
+    }
+}
+
+fn remote_ip<B>(req: &Request<B>) -> Option<IpAddr> {
+    remote_addr(req).map(|addr| addr.ip())
+}
+
+/// Returns address of TCP peer, [`None`] for connections accepted on unix socket.
+pub(super) fn remote_addr<B>(req: &Request<B>) -> Option<SocketAddr> {
+    let extensions = req.extensions();
+
+    #[cfg(feature = "tls")]
+    if let Some(ConnectInfo(crate::application::tls::RemoteAddr(addr))) =
+        extensions.get::<ConnectInfo<crate::application::tls::RemoteAddr>>()
+    {
+        return Some(*addr);
+    }
+
+    extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr)
+}
+
+fn constant_time_eq(expected: &str, actual: &str) -> bool {
+    let (expected, actual) = (expected.as_bytes(), actual.as_bytes());
+
+    expected.len() == actual.len()
+        && expected
+            .iter()
+            .zip(actual)
+            .fold(0, |acc, (left, right)| acc | (left ^ right))
+            == 0
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod auth_test {
+    use super::*;
+    use axum::middleware::from_fn;
+    use axum::routing::get;
+    use axum::Router;
+    use hyper::Body;
+    use std::net::Ipv4Addr;
+    use tower::ServiceExt;
+
+    fn router(auth_cfg: ManagementAuthConfig) -> Router {
+        let auth = ManagementAuth::new(&auth_cfg).unwrap();
+        Router::new()
+            .route("/metrics", get(|| async { "metrics" }))
+            .layer(from_fn(move |req, next| auth.clone().check(req, next)))
+    }
+
+    fn request(authorization: Option<&str>, ip: Option<[u8; 4]>) -> Request<Body> {
+        let mut request = Request::get("/metrics");
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+
+        let mut request = request.body(Body::empty()).unwrap();
+        if let Some(ip) = ip {
+            let addr = SocketAddr::from((Ipv4Addr::from(ip), 1234));
+            request.extensions_mut().insert(ConnectInfo(addr));
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn checks_credentials() {
+        let router = router(ManagementAuthConfig {
+            token: Some("secret".to_owned()),
+            basic_username: Some("admin".to_owned()),
+            basic_password: Some("password".to_owned()),
+            ..Default::default()
+        });
+
+        for (authorization, status) in [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some("Bearer wrong"), StatusCode::UNAUTHORIZED),
+            (Some("Bearer secret"), StatusCode::OK),
+            (Some("Basic YWRtaW46cGFzc3dvcmQ="), StatusCode::OK),
+            (Some("Basic YWRtaW46d3Jvbmc="), StatusCode::UNAUTHORIZED),
+        ] {
+            let response = router
+                .clone()
+                .oneshot(request(authorization, None))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status, "{authorization:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn checks_allowed_ips() {
+        let router = router(ManagementAuthConfig {
+            allowed_ips: Some(vec!["10.0.0.0/8".parse().unwrap()]),
+            ..Default::default()
+        });
+
+        for (ip, status) in [
+            (None, StatusCode::FORBIDDEN),
+            (Some([127, 0, 0, 1]), StatusCode::FORBIDDEN),
+            (Some([10, 1, 2, 3]), StatusCode::OK),
+        ] {
+            let response = router.clone().oneshot(request(None, ip)).await.unwrap();
+            assert_eq!(response.status(), status, "{ip:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn username_without_password_rejects_all() {
+        let router = router(ManagementAuthConfig {
+            basic_username: Some("admin".to_owned()),
+            ..Default::default()
+        });
+
+        // `admin:`
+        for authorization in [None, Some("Basic YWRtaW46"), Some("Basic ")] {
+            let response = router
+                .clone()
+                .oneshot(request(authorization, None))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+}
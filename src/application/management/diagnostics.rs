@@ -0,0 +1,142 @@
+//! Human-readable snapshot of runtime state for troubleshooting hung services.
+use crate::application::supervisor::Supervisor;
+use axum::routing::get;
+use axum::Router;
+use std::fmt::Write;
+use tokio::runtime::Handle;
+use tokio_metrics::RuntimeMonitor;
+
+pub(crate) fn build_diagnostics_router(endpoint: &str, supervisor: Supervisor) -> Router {
+    Router::new().route(
+        endpoint,
+        get(move || std::future::ready(render_diagnostics(&supervisor))),
+    )
+}
+
+/// Renders tokio runtime metrics, supervised background tasks and process threads.
+pub(crate) fn render_diagnostics(supervisor: &Supervisor) -> String {
+    let mut out = String::new();
+
+    render_runtime(&mut out);
+    render_tasks(&mut out, supervisor);
+    render_threads(&mut out);
+
+    out
+}
+
+fn render_runtime(out: &mut String) {
+    let _ = writeln!(out, "runtime:");
+
+    let Ok(handle) = Handle::try_current() else {
+        let _ = writeln!(out, "  not available outside of tokio runtime");
+        return;
+    };
+
+    let Some(metrics) = RuntimeMonitor::new(&handle).intervals().next() else {
+        let _ = writeln!(out, "  not available");
+        return;
+    };
+
+    let _ = writeln!(out, "  flavor: {:?}", handle.runtime_flavor());
+    let _ = writeln!(out, "  workers: {}", metrics.workers_count);
+    let _ = writeln!(
+        out,
+        "  injection queue depth: {}",
+        metrics.injection_queue_depth
+    );
+    let _ = writeln!(
+        out,
+        "  local queue depth: total {}, max {}, min {}",
+        metrics.total_local_queue_depth,
+        metrics.max_local_queue_depth,
+        metrics.min_local_queue_depth
+    );
+    let _ = writeln!(out, "  remote schedules: {}", metrics.num_remote_schedules);
+    let _ = writeln!(out, "  polls: {}", metrics.total_polls_count);
+    let _ = writeln!(
+        out,
+        "  busy duration: total {:?}, max {:?}, min {:?}",
+        metrics.total_busy_duration, metrics.max_busy_duration, metrics.min_busy_duration
+    );
+}
+
+fn render_tasks(out: &mut String, supervisor: &Supervisor) {
+    let tasks = supervisor.tasks();
+    let _ = writeln!(out, "background tasks: {}", tasks.len());
+
+    for (name, task) in tasks {
+        let _ = write!(out, "  {name}: {}, restarts {}", task.state, task.restarts);
+        if let Some(err) = task.last_error {
+            let _ = write!(out, ", last error: {err}");
+        }
+        let _ = writeln!(out);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn render_threads(out: &mut String) {
+    let Ok(entries) = std::fs::read_dir("/proc/self/task") else {
+        let _ = writeln!(out, "threads: not available");
+        return;
+    };
+
+    let mut threads = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let tid = entry.file_name().to_string_lossy().parse::<u32>().ok()?;
+            let path = entry.path();
+            let read = |file: &str| {
+                std::fs::read_to_string(path.join(file))
+                    .map(|content| content.trim().to_owned())
+                    .unwrap_or_default()
+            };
+
+            // state goes right after `(comm)` in stat, comm itself might contain spaces and braces.
+            let stat = read("stat");
+            let state = stat
+                .rsplit_once(')')
+                .and_then(|(_, rest)| rest.split_whitespace().next())
+                .unwrap_or("?")
+                .to_owned();
+
+            Some((tid, read("comm"), state, read("wchan")))
+        })
+        .collect::<Vec<_>>();
+    threads.sort();
+
+    let _ = writeln!(out, "threads: {}", threads.len());
+    for (tid, name, state, wchan) in threads {
+        let _ = write!(out, "  {tid} {name}: {state}");
+        if !wchan.is_empty() && wchan != "0" {
+            let _ = write!(out, ", waiting in {wchan}");
+        }
+        let _ = writeln!(out);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn render_threads(out: &mut String) {
+    let _ = writeln!(out, "threads: not available on this platform");
+}
+
+#[cfg(test)]
+mod diagnostics_test {
+    use super::*;
+    use crate::application::supervisor::RestartPolicy;
+
+    #[tokio::test]
+    async fn renders_snapshot() {
+        let supervisor = Supervisor::default();
+        supervisor.register("consumer", RestartPolicy::default(), |_token| async {
+            Ok::<_, String>(())
+        });
+
+        let out = render_diagnostics(&supervisor);
+
+        assert!(out.contains("runtime:\n"));
+        assert!(out.contains("  workers: 1\n"));
+        assert!(out.contains("background tasks: 1\n  consumer: pending, restarts 0\n"));
+        #[cfg(target_os = "linux")]
+        assert!(out.contains("threads: "));
+    }
+}
@@ -0,0 +1,424 @@
+//! Management endpoint to read and change log and trace filters at runtime.
+use super::auth::remote_addr;
+use crate::application::shutdown::Shutdown;
+use crate::configuration::units::Millis;
+use crate::observability::{
+    reloaded_levels, LogLayerHandle, TraceLayerHandle, LOG_LAYER_HANDLE, OTLP_LAYER_HANDLE,
+};
+use crate::{LogLevelConfig, ObservabilityConfig};
+use axum::body::Body;
+use axum::extract::FromRequest;
+use axum::http::{Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tracing_subscriber::EnvFilter;
+
+/// Current filter directives, [`None`] if layer is not initialised.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct LogLevels {
+    pub(crate) log: Option<String>,
+    pub(crate) trace: Option<String>,
+}
+
+/// Body of `PUT` request. Filters which are not set stay unchanged.\
+/// If `ttl` (number of milliseconds or string like `30s`) is set, both filters are reverted to currently configured levels
+/// after it expires or once application shuts down, including levels applied by [`crate::ConfigWatcher`] in the meantime.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub(crate) struct LogLevelsUpdate {
+    #[serde(default)]
+    pub(crate) log: Option<String>,
+    #[serde(default)]
+    pub(crate) trace: Option<String>,
+    #[serde(default)]
+    pub(crate) ttl: Option<Millis>,
+}
+
+/// Returns configured log and trace levels.
+type Configured = Arc<dyn Fn() -> (String, String) + Send + Sync>;
+
+#[derive(Clone)]
+pub(crate) struct LogLevelState {
+    log: Option<LogLayerHandle>,
+    trace: Option<TraceLayerHandle>,
+    configured: Configured,
+    shutdown: Shutdown,
+    revert: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl LogLevelState {
+    pub(crate) fn new(observability_cfg: &ObservabilityConfig, shutdown: Shutdown) -> Self {
+        let log_level = observability_cfg.logger_config.log_level.clone();
+        let trace_level = observability_cfg.trace_level.clone();
+
+        Self::with_handles(
+            LOG_LAYER_HANDLE.get().cloned(),
+            OTLP_LAYER_HANDLE.get().cloned(),
+            Arc::new(move || {
+                reloaded_levels().unwrap_or_else(|| (log_level.clone(), trace_level.clone()))
+            }),
+            shutdown,
+        )
+    }
+
+    fn with_handles(
+        log: Option<LogLayerHandle>,
+        trace: Option<TraceLayerHandle>,
+        configured: Configured,
+        shutdown: Shutdown,
+    ) -> Self {
+        Self {
+            log,
+            trace,
+            configured,
+            shutdown,
+            revert: Default::default(),
+        }
+    }
+
+    /// Returns configured levels, only for initialised layers as only they are reverted after ttl.
+    fn configured(&self) -> LogLevels {
+        let (log, trace) = (self.configured)();
+
+        LogLevels {
+            log: self.log.as_ref().map(|_| log),
+            trace: self.trace.as_ref().map(|_| trace),
+        }
+    }
+
+    fn current(&self) -> LogLevels {
+        LogLevels {
+            log: self
+                .log
+                .as_ref()
+                .and_then(|handle| handle.with_current(ToString::to_string).ok()),
+            trace: self
+                .trace
+                .as_ref()
+                .and_then(|handle| handle.with_current(ToString::to_string).ok()),
+        }
+    }
+
+    fn apply(&self, levels: &LogLevels) -> Result<(), (StatusCode, String)> {
+        let parse = |directives: &Option<String>| {
+            directives
+                .as_deref()
+                .map(EnvFilter::from_str)
+                .transpose()
+                .map_err(|err| (StatusCode::BAD_REQUEST, format!("Invalid filter: {err}.")))
+        };
+        let log = parse(&levels.log)?
+            .map(|filter| Ok((initialised(self.log.as_ref(), "Log")?, filter)))
+            .transpose()?;
+        let trace = parse(&levels.trace)?
+            .map(|filter| Ok((initialised(self.trace.as_ref(), "Trace")?, filter)))
+            .transpose()?;
+
+        if let Some((handle, filter)) = log {
+            reload(handle, filter)?;
+        }
+        if let Some((handle, filter)) = trace {
+            reload(handle, filter)?;
+        }
+
+        Ok(())
+    }
+
+    fn update(
+        &self,
+        update: LogLevelsUpdate,
+        caller: Option<SocketAddr>,
+    ) -> Result<LogLevels, (StatusCode, String)> {
+        let LogLevelsUpdate { log, trace, ttl } = update;
+        let ttl = ttl.map(|Millis(ttl)| ttl);
+        if log.is_none() && trace.is_none() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "At least one of `log` or `trace` must be set.".to_owned(),
+            ));
+        }
+
+        let previous = self.current();
+        self.apply(&LogLevels { log, trace })?;
+        let current = self.current();
+
+        tracing::warn!(
+            caller = caller.map_or_else(|| "unknown".to_owned(), |addr| addr.to_string()),
+            previous_log = previous.log,
+            previous_trace = previous.trace,
+            log = current.log,
+            trace = current.trace,
+            ttl = ?ttl,
+            "Log levels changed through management endpoint."
+        );
+
+        let revert = ttl.map(|ttl| {
+            let state = self.clone();
+            let shutdown = self.shutdown.subscribe();
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = tokio::time::sleep(ttl) => {},
+                    _ = shutdown => {},
+                }
+
+                let configured = state.configured();
+                match state.apply(&configured) {
+                    Ok(()) => tracing::warn!(
+                        log = configured.log,
+                        trace = configured.trace,
+                        "Log levels reverted to configured after ttl expired or on shutdown."
+                    ),
+                    Err((_, err)) => tracing::error!("Could not revert log levels: {err}"),
+                }
+            })
+        });
+
+        if let Ok(mut guard) = self.revert.lock() {
+            if let Some(previous) = std::mem::replace(&mut *guard, revert) {
+                previous.abort();
+            }
+        }
+
+        Ok(current)
+    }
+}
+
+fn initialised<'a, H>(handle: Option<&'a H>, name: &str) -> Result<&'a H, (StatusCode, String)> {
+    handle.ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("{name} layer is not initialised."),
+        )
+    })
+}
+
+fn reload<S>(
+    handle: &tracing_subscriber::reload::Handle<EnvFilter, S>,
+    filter: EnvFilter,
+) -> Result<(), (StatusCode, String)> {
+    handle
+        .reload(filter)
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+}
+
+async fn get_levels(state: Extension<LogLevelState>) -> Json<LogLevels> {
+    Json(state.current())
+}
+
+async fn put_levels(state: Extension<LogLevelState>, request: Request<Body>) -> Response {
+    let caller = remote_addr(&request);
+    let update = match Json::<LogLevelsUpdate>::from_request(request, &()).await {
+        Ok(Json(update)) => update,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    match state.update(update, caller) {
+        Ok(levels) => Json(levels).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+/// Levels might be changed with `PUT` only if [`crate::LogLevelConfig::enabled`] is set.
+pub(crate) fn build_log_level_router(
+    endpoint: &str,
+    loglevel_cfg: &LogLevelConfig,
+    state: LogLevelState,
+) -> Router {
+    let method = if loglevel_cfg.enabled {
+        get(get_levels).put(put_levels)
+    } else {
+        get(get_levels)
+    };
+
+    Router::new()
+        .route(endpoint, method)
+        .layer(Extension(state))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod log_level_test {
+    use super::*;
+    use std::time::Duration;
+    use tower::ServiceExt;
+    use tracing_subscriber::reload;
+
+    use tracing_subscriber::layer::Layered;
+    use tracing_subscriber::{Layer, Registry};
+
+    type LogSubscriber = Layered<Option<Box<dyn Layer<Registry> + Send + Sync>>, Registry>;
+    type Layers = (
+        reload::Layer<EnvFilter, LogSubscriber>,
+        reload::Layer<EnvFilter, Registry>,
+    );
+
+    type Configured = Arc<Mutex<(String, String)>>;
+
+    // handles stay valid only while layers are alive.
+    fn state() -> (LogLevelState, Configured, Layers) {
+        state_with(Shutdown::new(Default::default()))
+    }
+
+    fn state_with(shutdown: Shutdown) -> (LogLevelState, Configured, Layers) {
+        let (log_layer, log): (_, LogLayerHandle) = reload::Layer::new(EnvFilter::new("info"));
+        let (trace_layer, trace): (_, TraceLayerHandle) =
+            reload::Layer::new(EnvFilter::new("info"));
+
+        let configured = Arc::new(Mutex::new(("info".to_owned(), "warn".to_owned())));
+        let state = LogLevelState::with_handles(
+            Some(log),
+            Some(trace),
+            {
+                let configured = configured.clone();
+                Arc::new(move || configured.lock().unwrap().clone())
+            },
+            shutdown,
+        );
+        (state, configured, (log_layer, trace_layer))
+    }
+
+    #[tokio::test]
+    async fn updates_and_reverts_after_ttl() {
+        let (state, _configured, _layers) = state();
+
+        let levels = state
+            .update(
+                LogLevelsUpdate {
+                    log: Some("debug".to_owned()),
+                    trace: None,
+                    ttl: Some(Millis(Duration::from_millis(50))),
+                },
+                None,
+            )
+            .unwrap();
+        assert_eq!(levels.log.as_deref(), Some("debug"));
+        assert_eq!(levels.trace.as_deref(), Some("info"));
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let levels = state.current();
+        assert_eq!(levels.log.as_deref(), Some("info"));
+        assert_eq!(levels.trace.as_deref(), Some("warn"));
+    }
+
+    #[tokio::test]
+    async fn reverts_to_reloaded_levels() {
+        let (state, configured, _layers) = state();
+
+        state
+            .update(
+                LogLevelsUpdate {
+                    log: Some("debug".to_owned()),
+                    trace: None,
+                    ttl: Some(Millis(Duration::from_millis(50))),
+                },
+                Some(SocketAddr::from(([127, 0, 0, 1], 8000))),
+            )
+            .unwrap();
+        *configured.lock().unwrap() = ("error".to_owned(), "error".to_owned());
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let levels = state.current();
+        assert_eq!(levels.log.as_deref(), Some("error"));
+        assert_eq!(levels.trace.as_deref(), Some("error"));
+    }
+
+    #[tokio::test]
+    async fn reverts_on_shutdown() {
+        let shutdown = Shutdown::new(Default::default());
+        let (state, _configured, _layers) = state_with(shutdown.clone());
+
+        state
+            .update(
+                LogLevelsUpdate {
+                    log: Some("debug".to_owned()),
+                    trace: None,
+                    ttl: Some(Millis(Duration::from_secs(60))),
+                },
+                None,
+            )
+            .unwrap();
+        tokio::spawn(shutdown.run(
+            std::future::pending::<crate::error::Result<()>>(),
+            std::future::ready(()),
+        ));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(state.current().log.as_deref(), Some("info"));
+    }
+
+    #[test]
+    fn ttl_accepts_duration_string() {
+        let update: LogLevelsUpdate =
+            serde_json::from_str(r#"{"log": "debug", "ttl": "30s"}"#).unwrap();
+        assert_eq!(update.ttl, Some(Millis(Duration::from_secs(30))));
+
+        let update: LogLevelsUpdate = serde_json::from_str(r#"{"ttl": 500}"#).unwrap();
+        assert_eq!(update.ttl, Some(Millis(Duration::from_millis(500))));
+    }
+
+    #[tokio::test]
+    async fn put_is_disabled_by_default() {
+        let (state, _configured, _layers) = state();
+        let request = |method| {
+            Request::builder()
+                .method(method)
+                .uri("/loglevel")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"log":"debug"}"#))
+                .unwrap()
+        };
+
+        let router = build_log_level_router("/loglevel", &LogLevelConfig::default(), state.clone());
+        let response = router.clone().oneshot(request("GET")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = router.oneshot(request("PUT")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let router = build_log_level_router("/loglevel", &LogLevelConfig { enabled: true }, state);
+        let response = router.oneshot(request("PUT")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_update() {
+        let (state, _configured, _layers) = state();
+
+        let (status, _) = state.update(LogLevelsUpdate::default(), None).unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = state
+            .update(
+                LogLevelsUpdate {
+                    log: Some("debug".to_owned()),
+                    trace: Some("[=".to_owned()),
+                    ttl: None,
+                },
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(state.current().log.as_deref(), Some("info"));
+
+        let missing = LogLevelState::with_handles(
+            None,
+            None,
+            Arc::new(|| ("info".to_owned(), "info".to_owned())),
+            Shutdown::new(Default::default()),
+        );
+        let (status, _) = missing
+            .update(
+                LogLevelsUpdate {
+                    log: Some("debug".to_owned()),
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
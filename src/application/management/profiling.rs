@@ -0,0 +1,133 @@
+//! Sampling CPU profiler endpoints compatible with `go tool pprof`.
+use axum::extract::Query;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use pprof::protos::Message;
+use pprof::{ProfilerGuardBuilder, Report};
+use serde::Deserialize;
+use std::time::Duration;
+
+const DEFAULT_SECONDS: u64 = 30;
+const MAX_SECONDS: u64 = 300;
+const DEFAULT_FREQUENCY: i32 = 99;
+const BLOCKLIST: [&str; 4] = ["libc", "libgcc", "pthread", "vdso"];
+
+/// Query parameters of profiling endpoints, e.g. `?seconds=10&frequency=99`.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub(crate) struct ProfileParams {
+    seconds: Option<u64>,
+    frequency: Option<i32>,
+}
+
+pub(crate) fn build_profiling_router(profile: &str, flamegraph: &str) -> Router {
+    Router::new()
+        .route(profile, get(profile_handler))
+        .route(flamegraph, get(flamegraph_handler))
+}
+
+async fn profile_handler(Query(params): Query<ProfileParams>) -> Response {
+    let profile = profile(params)
+        .await
+        .and_then(|report| report.pprof().map_err(internal_error));
+
+    match profile {
+        Ok(profile) => (
+            [(CONTENT_TYPE, "application/octet-stream")],
+            profile.encode_to_vec(),
+        )
+            .into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+async fn flamegraph_handler(Query(params): Query<ProfileParams>) -> Response {
+    let flamegraph = profile(params).await.and_then(|report| {
+        let mut svg = Vec::new();
+        report.flamegraph(&mut svg).map_err(internal_error)?;
+        Ok(svg)
+    });
+
+    match flamegraph {
+        Ok(svg) => ([(CONTENT_TYPE, "image/svg+xml")], svg).into_response(),
+        Err(err) => err.into_response(),
+    }
+}
+
+/// Samples CPU for requested duration on blocking thread, only one profile might be taken at a time.
+async fn profile(params: ProfileParams) -> Result<Report, (StatusCode, String)> {
+    let seconds = params.seconds.unwrap_or(DEFAULT_SECONDS);
+    if seconds == 0 || seconds > MAX_SECONDS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("`seconds` must be in range 1..={MAX_SECONDS}."),
+        ));
+    }
+
+    let frequency = params.frequency.unwrap_or(DEFAULT_FREQUENCY);
+    if frequency <= 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "`frequency` must be positive.".to_owned(),
+        ));
+    }
+
+    tracing::info!(seconds, frequency, "CPU profiling started.");
+    tokio::task::spawn_blocking(move || {
+        let guard = ProfilerGuardBuilder::default()
+            .frequency(frequency)
+            .blocklist(&BLOCKLIST)
+            .build()
+            .map_err(|err| (StatusCode::CONFLICT, err.to_string()))?;
+
+        std::thread::sleep(Duration::from_secs(seconds));
+        guard.report().build().map_err(internal_error)
+    })
+    .await
+    .map_err(internal_error)?
+}
+
+fn internal_error(err: impl ToString) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod profiling_test {
+    use super::*;
+
+    #[tokio::test]
+    async fn rejects_invalid_params() {
+        for params in [
+            ProfileParams {
+                seconds: Some(0),
+                frequency: None,
+            },
+            ProfileParams {
+                seconds: Some(MAX_SECONDS + 1),
+                frequency: None,
+            },
+            ProfileParams {
+                seconds: Some(1),
+                frequency: Some(0),
+            },
+        ] {
+            let (status, _) = profile(params).await.unwrap_err();
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn renders_flamegraph() {
+        let response = flamegraph_handler(Query(ProfileParams {
+            seconds: Some(1),
+            frequency: None,
+        }))
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "image/svg+xml");
+    }
+}
@@ -0,0 +1,86 @@
+//! Handle to [`crate::Application`] served in background.
+use crate::application::listener::ListenerAddr;
+use crate::error::{Error, Result};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Handle to [`crate::Application`] returned by [`crate::Application::bind`].\
+/// Listeners are already bound and accepting connections once handle is returned.\
+/// Awaiting the handle resolves with the result of serving once application is stopped.
+/// Dropping the handle triggers graceful shutdown, same as [`RunningApplication::shutdown`].
+///
+/// Example:
+/// ```no_run
+/// use fregate::{tokio, AppConfig, Application};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = AppConfig {
+///         port: 0,
+///         ..AppConfig::default()
+///     };
+///
+///     let running = Application::new(config).bind().await.unwrap();
+///     println!("Listening on: {}", running.local_addr());
+///
+///     running.shutdown();
+///     running.await.unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RunningApplication {
+    local_addr: ListenerAddr,
+    management_addr: Option<ListenerAddr>,
+    trigger: CancellationToken,
+    handle: JoinHandle<Result<()>>,
+}
+
+impl RunningApplication {
+    pub(crate) fn new(
+        local_addr: ListenerAddr,
+        management_addr: Option<ListenerAddr>,
+        trigger: CancellationToken,
+        handle: JoinHandle<Result<()>>,
+    ) -> Self {
+        Self {
+            local_addr,
+            management_addr,
+            trigger,
+            handle,
+        }
+    }
+
+    /// Returns address application is accepting connections on.
+    pub fn local_addr(&self) -> &ListenerAddr {
+        &self.local_addr
+    }
+
+    /// Returns address management endpoints are served on if they are served by a separate listener.
+    pub fn management_addr(&self) -> Option<&ListenerAddr> {
+        self.management_addr.as_ref()
+    }
+
+    /// Triggers graceful shutdown, same as termination signal would.
+    pub fn shutdown(&self) {
+        self.trigger.cancel();
+    }
+}
+
+impl Future for RunningApplication {
+    type Output = Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.handle)
+            .poll(cx)
+            .map(|joined| joined.map_err(Error::from).and_then(|result| result))
+    }
+}
+
+impl Drop for RunningApplication {
+    fn drop(&mut self) {
+        self.trigger.cancel();
+    }
+}
@@ -0,0 +1,317 @@
+use crate::application::health::HealthExt;
+use crate::application::lifecycle::Startup;
+use crate::application::supervisor::Supervisor;
+use crate::configuration::ShutdownConfig;
+use crate::error::Result;
+use axum::http::{Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::signal;
+use tokio::sync::watch;
+use tokio::time::{sleep, timeout};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ShutdownPhase {
+    Running,
+    PreStop,
+    Draining,
+    Terminated,
+}
+
+#[derive(Debug)]
+struct Inner {
+    config: ShutdownConfig,
+    phase: watch::Sender<ShutdownPhase>,
+    in_flight: AtomicUsize,
+}
+
+/// Drives shutdown sequence of every listener started by [`crate::Application`]:
+/// 1. on termination signal `/ready` starts to report unavailable for [`ShutdownConfig::pre_stop_delay`].
+/// 2. listeners stop accepting new connections and drain existing ones.
+/// 3. after [`ShutdownConfig::drain_timeout`] remaining connections are forcibly closed.
+#[derive(Debug, Clone)]
+pub(crate) struct Shutdown {
+    inner: Arc<Inner>,
+}
+
+impl Shutdown {
+    pub(crate) fn new(config: ShutdownConfig) -> Self {
+        let (phase, _) = watch::channel(ShutdownPhase::Running);
+
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                phase,
+                in_flight: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Returns future which resolves once listeners should stop accepting new connections.
+    pub(crate) fn subscribe(&self) -> impl Future<Output = ()> + Send + 'static {
+        self.wait_for(ShutdownPhase::Draining)
+    }
+
+    /// Returns [`hyper::rt::Executor`] which drops spawned connections once drain timeout elapsed.
+    pub(crate) fn executor(&self) -> ShutdownExecutor {
+        ShutdownExecutor {
+            shutdown: self.clone(),
+        }
+    }
+
+    pub(crate) fn drain_timeout(&self) -> Option<Duration> {
+        self.inner.config.drain_timeout
+    }
+
+    pub(crate) fn is_running(&self) -> bool {
+        *self.inner.phase.borrow() == ShutdownPhase::Running
+    }
+
+    /// Middleware counting in-flight requests, to report how many of them are dropped on drain timeout.
+    pub(crate) async fn track_in_flight<B>(self, req: Request<B>, next: Next<B>) -> Response {
+        self.inner.in_flight.fetch_add(1, Ordering::Relaxed);
+        let _guard = InFlightGuard(&self.inner.in_flight);
+
+        next.run(req).await
+    }
+
+    /// Polls `servers` until `signal` resolves and then runs shutdown sequence.
+    pub(crate) async fn run(
+        self,
+        servers: impl Future<Output = Result<()>>,
+        signal: impl Future<Output = ()>,
+    ) -> Result<()> {
+        tokio::pin!(servers);
+
+        tokio::select! {
+            result = &mut servers => return result,
+            _ = signal => {},
+        }
+
+        let ShutdownConfig {
+            pre_stop_delay,
+            drain_timeout,
+        } = self.inner.config;
+
+        self.set_phase(ShutdownPhase::PreStop);
+        if !pre_stop_delay.is_zero() {
+            info!("Pre-stop delay: `{pre_stop_delay:?}`, reporting not ready.");
+
+            tokio::select! {
+                result = &mut servers => return result,
+                _ = sleep(pre_stop_delay) => {},
+            }
+        }
+
+        self.set_phase(ShutdownPhase::Draining);
+        info!("Stop accepting new connections, draining...");
+
+        let Some(drain_timeout) = drain_timeout else {
+            return servers.await;
+        };
+
+        match timeout(drain_timeout, &mut servers).await {
+            Ok(result) => result,
+            Err(_elapsed) => {
+                let dropped = self.inner.in_flight.load(Ordering::Relaxed);
+                warn!("Drain timeout `{drain_timeout:?}` elapsed, closing connections. In-flight requests dropped: `{dropped}`.");
+
+                self.set_phase(ShutdownPhase::Terminated);
+                servers.await
+            }
+        }
+    }
+
+    fn set_phase(&self, phase: ShutdownPhase) {
+        self.inner.phase.send_replace(phase);
+    }
+
+    fn wait_for(&self, phase: ShutdownPhase) -> impl Future<Output = ()> + Send + 'static {
+        let mut receiver = self.inner.phase.subscribe();
+
+        async move {
+            let _ = receiver.wait_for(|current| *current >= phase).await;
+        }
+    }
+}
+
+struct InFlightGuard<'a>(&'a AtomicUsize);
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Spawns hyper connections on tokio runtime and drops them on forced shutdown.
+#[derive(Debug, Clone)]
+pub(crate) struct ShutdownExecutor {
+    shutdown: Shutdown,
+}
+
+impl<F> hyper::rt::Executor<F> for ShutdownExecutor
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    fn execute(&self, fut: F) {
+        let terminated = self.shutdown.wait_for(ShutdownPhase::Terminated);
+
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = fut => {},
+                _ = terminated => {},
+            }
+        });
+    }
+}
+
+/// Wraps [`HealthExt`] to report not alive if any supervised background task failed,
+/// not started and not ready until warm-up finishes and not ready once shutdown has started.
+#[derive(Debug, Clone)]
+pub(crate) struct ApplicationHealth<H> {
+    health: H,
+    shutdown: Shutdown,
+    supervisor: Supervisor,
+    startup: Startup,
+}
+
+impl<H> ApplicationHealth<H> {
+    pub(crate) fn new(
+        health: H,
+        shutdown: Shutdown,
+        supervisor: Supervisor,
+        startup: Startup,
+    ) -> Self {
+        Self {
+            health,
+            shutdown,
+            supervisor,
+            startup,
+        }
+    }
+}
+
+#[axum::async_trait]
+impl<H: HealthExt> HealthExt for ApplicationHealth<H> {
+    type HealthResponse = Response;
+    type ReadyResponse = Response;
+
+    async fn alive(&self) -> Self::HealthResponse {
+        if self.supervisor.is_alive() {
+            self.health.alive().await.into_response()
+        } else {
+            let tasks = Json(self.supervisor.tasks());
+            (StatusCode::SERVICE_UNAVAILABLE, tasks).into_response()
+        }
+    }
+
+    async fn ready(&self) -> Self::ReadyResponse {
+        if !self.shutdown.is_running() {
+            (StatusCode::SERVICE_UNAVAILABLE, "UNAVAILABLE").into_response()
+        } else if !self.startup.is_started() {
+            (StatusCode::SERVICE_UNAVAILABLE, "STARTING").into_response()
+        } else {
+            self.health.ready().await.into_response()
+        }
+    }
+
+    async fn started(&self) -> Response {
+        if self.startup.is_started() {
+            self.health.started().await
+        } else {
+            (StatusCode::SERVICE_UNAVAILABLE, "STARTING").into_response()
+        }
+    }
+}
+
+#[allow(clippy::expect_used)]
+pub(crate) async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Termination signal, starting shutdown...");
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod shutdown_test {
+    use super::*;
+
+    #[tokio::test]
+    async fn pre_stop_delay_reports_not_ready() {
+        let shutdown = Shutdown::new(ShutdownConfig {
+            pre_stop_delay: Duration::from_millis(200),
+            drain_timeout: None,
+        });
+        let health = ApplicationHealth::new(
+            crate::health::AlwaysReadyAndAlive,
+            shutdown.clone(),
+            Supervisor::default(),
+            crate::application::lifecycle::Lifecycle::default().startup(),
+        );
+        let stopped = shutdown.subscribe();
+
+        let servers = async move {
+            stopped.await;
+            Ok(())
+        };
+        let handle = tokio::spawn(shutdown.clone().run(servers, async {}));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            health.ready().await.status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert!(!handle.is_finished());
+
+        handle.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn drain_timeout_drops_connections() {
+        let shutdown = Shutdown::new(ShutdownConfig {
+            pre_stop_delay: Duration::ZERO,
+            drain_timeout: Some(Duration::from_millis(50)),
+        });
+
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        hyper::rt::Executor::execute(&shutdown.executor(), async move {
+            let _sender = sender;
+            std::future::pending::<()>().await
+        });
+
+        let servers = async move {
+            // connection future is dropped by executor, so sender is dropped as well.
+            let _ = receiver.await;
+            Ok(())
+        };
+
+        shutdown.run(servers, async {}).await.unwrap();
+    }
+}
@@ -0,0 +1,445 @@
+//! Supervision of background tasks owned by [`crate::Application`].
+//!
+//! See [`crate::Application::background_task`] for more information.
+use metrics::{describe_counter, increment_counter};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout_at};
+use tracing::{error, info, warn};
+
+pub use tokio_util::sync::CancellationToken;
+
+const RESTARTS_METRIC: &str = "background_task_restarts";
+/// Used by [`Supervisor::stop`] if no timeout is given, so tasks ignoring cancellation can't block shutdown.
+const DEFAULT_STOP_TIMEOUT: Duration = Duration::from_secs(30);
+
+type TaskFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'static>>;
+type TaskFactory = Arc<dyn Fn(CancellationToken) -> TaskFuture + Send + Sync + 'static>;
+
+/// When supervised task should be restarted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Restart {
+    /// Never restart the task.
+    Never,
+    /// Restart only if task returned error or panicked.
+    OnFailure,
+    /// Restart the task whenever it stops.
+    Always,
+}
+
+/// Restart policy for supervised background task.\
+/// Delay between restarts starts with `initial_backoff` and doubles on each consecutive restart up to `max_backoff`.
+/// If task was running longer than `max_backoff` delay is reset to `initial_backoff`.
+#[derive(Debug, Clone, Copy)]
+pub struct RestartPolicy {
+    /// when task should be restarted.
+    pub restart: Restart,
+    /// delay before first restart.
+    pub initial_backoff: Duration,
+    /// maximum delay between restarts.
+    pub max_backoff: Duration,
+    /// maximum number of restarts, unlimited if [`None`].
+    pub max_restarts: Option<usize>,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            restart: Restart::OnFailure,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+            max_restarts: None,
+        }
+    }
+}
+
+/// Current state of supervised background task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    /// Task is registered but not started yet.
+    Pending,
+    /// Task is running.
+    Running,
+    /// Task stopped and is waiting for restart.
+    Restarting,
+    /// Task finished successfully and won't be restarted.
+    Finished,
+    /// Task failed and won't be restarted.
+    Failed,
+    /// Task was cancelled on shutdown.
+    Stopped,
+}
+
+impl TaskState {
+    /// Returns `false` only if task failed and won't be restarted.
+    pub fn is_alive(&self) -> bool {
+        !matches!(self, TaskState::Failed)
+    }
+}
+
+impl Display for TaskState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            TaskState::Pending => "pending",
+            TaskState::Running => "running",
+            TaskState::Restarting => "restarting",
+            TaskState::Finished => "finished",
+            TaskState::Failed => "failed",
+            TaskState::Stopped => "stopped",
+        };
+
+        f.write_str(state)
+    }
+}
+
+/// Snapshot of supervised background task.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskInfo {
+    /// current state.
+    pub state: TaskState,
+    /// how many times task was restarted.
+    pub restarts: usize,
+    /// last error returned by task, if any.
+    pub last_error: Option<String>,
+}
+
+struct TaskSpec {
+    name: String,
+    policy: RestartPolicy,
+    factory: TaskFactory,
+}
+
+/// Supervises background tasks, restarts them according to [`RestartPolicy`] and cancels them on shutdown.
+#[derive(Clone)]
+pub struct Supervisor {
+    tasks: Arc<Mutex<BTreeMap<String, TaskInfo>>>,
+    specs: Arc<Mutex<Vec<TaskSpec>>>,
+    handles: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    token: CancellationToken,
+}
+
+impl Debug for Supervisor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Supervisor")
+            .field("tasks", &self.tasks())
+            .field("cancelled", &self.token.is_cancelled())
+            .finish()
+    }
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self {
+            tasks: Default::default(),
+            specs: Default::default(),
+            handles: Default::default(),
+            token: CancellationToken::new(),
+        }
+    }
+}
+
+impl Supervisor {
+    /// Returns snapshot of all registered tasks.
+    pub fn tasks(&self) -> BTreeMap<String, TaskInfo> {
+        lock(&self.tasks).clone()
+    }
+
+    /// Returns `true` if no task has failed permanently.
+    pub fn is_alive(&self) -> bool {
+        lock(&self.tasks).values().all(|task| task.state.is_alive())
+    }
+
+    /// Returns `true` if no tasks were registered.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.tasks).is_empty()
+    }
+
+    pub(crate) fn register<F, Fut, E>(&self, name: &str, policy: RestartPolicy, task: F)
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        let factory: TaskFactory = Arc::new(move |token| {
+            let fut = task(token);
+            Box::pin(async move { fut.await.map_err(|err| err.to_string()) })
+        });
+
+        let name = {
+            let mut tasks = lock(&self.tasks);
+            let name = unique_name(&tasks, name);
+            tasks.insert(
+                name.clone(),
+                TaskInfo {
+                    state: TaskState::Pending,
+                    restarts: 0,
+                    last_error: None,
+                },
+            );
+            name
+        };
+        lock(&self.specs).push(TaskSpec {
+            name,
+            policy,
+            factory,
+        });
+    }
+
+    /// Spawns all registered tasks.
+    pub(crate) fn start(&self) {
+        let specs = std::mem::take(&mut *lock(&self.specs));
+
+        if !specs.is_empty() {
+            describe_counter!(RESTARTS_METRIC, "Number of background task restarts.");
+        }
+
+        let mut handles = lock(&self.handles);
+        for spec in specs {
+            handles.push(tokio::spawn(self.clone().supervise(spec)));
+        }
+    }
+
+    /// Notifies all tasks to stop through [`CancellationToken`].
+    pub(crate) fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Cancels all tasks and waits until they stop.
+    /// If `wait_timeout` elapses, remaining tasks are aborted, timeout is shared by all tasks.
+    /// If `wait_timeout` is [`None`], tasks are aborted after 30 seconds.
+    pub(crate) async fn stop(&self, wait_timeout: Option<Duration>) {
+        self.cancel();
+
+        let deadline = tokio::time::Instant::now() + wait_timeout.unwrap_or(DEFAULT_STOP_TIMEOUT);
+        let handles = std::mem::take(&mut *lock(&self.handles));
+        for mut handle in handles {
+            if timeout_at(deadline, &mut handle).await.is_err() {
+                handle.abort();
+            }
+        }
+    }
+
+    async fn supervise(self, spec: TaskSpec) {
+        let TaskSpec {
+            name,
+            policy,
+            factory,
+        } = spec;
+        let mut backoff = policy.initial_backoff;
+
+        loop {
+            self.update(&name, |task| task.state = TaskState::Running);
+
+            let started = Instant::now();
+            // aborts task if supervisor itself is aborted on shutdown timeout.
+            let mut handle = AbortOnDrop(tokio::spawn(factory(self.token.child_token())));
+
+            let outcome = tokio::select! {
+                outcome = &mut handle.0 => outcome,
+                _ = self.token.cancelled() => (&mut handle.0).await,
+            };
+
+            let error = match outcome {
+                Ok(Ok(())) => None,
+                Ok(Err(err)) => Some(err),
+                Err(err) if err.is_panic() => Some(format!("task panicked: `{err}`")),
+                Err(err) => Some(err.to_string()),
+            };
+
+            if self.token.is_cancelled() {
+                info!("Background task `{name}` stopped.");
+                self.update(&name, |task| {
+                    task.state = TaskState::Stopped;
+                    task.last_error = error;
+                });
+                return;
+            }
+
+            let restarts = self.update(&name, |task| task.restarts);
+            let exhausted = policy.max_restarts.is_some_and(|max| restarts >= max);
+            let restart = match (&error, policy.restart) {
+                (_, Restart::Never) | (None, Restart::OnFailure) => false,
+                (Some(_), Restart::OnFailure) | (_, Restart::Always) => !exhausted,
+            };
+
+            if let Some(err) = &error {
+                error!("Background task `{name}` failed: `{err}`.");
+            }
+
+            if !restart {
+                let state = if error.is_some() {
+                    TaskState::Failed
+                } else {
+                    TaskState::Finished
+                };
+                self.update(&name, |task| {
+                    task.state = state;
+                    task.last_error = error;
+                });
+                return;
+            }
+
+            if started.elapsed() > policy.max_backoff {
+                backoff = policy.initial_backoff;
+            }
+
+            self.update(&name, |task| {
+                task.state = TaskState::Restarting;
+                task.last_error = error;
+            });
+            warn!("Restarting background task `{name}` in `{backoff:?}`.");
+
+            tokio::select! {
+                _ = sleep(backoff) => {},
+                _ = self.token.cancelled() => {
+                    self.update(&name, |task| task.state = TaskState::Stopped);
+                    return;
+                }
+            }
+
+            backoff = backoff.saturating_mul(2).min(policy.max_backoff);
+            self.update(&name, |task| task.restarts += 1);
+            increment_counter!(RESTARTS_METRIC, "task" => name.clone());
+        }
+    }
+
+    fn update<R>(&self, name: &str, f: impl FnOnce(&mut TaskInfo) -> R) -> R {
+        let mut tasks = lock(&self.tasks);
+        let task = tasks.entry(name.to_owned()).or_insert(TaskInfo {
+            state: TaskState::Pending,
+            restarts: 0,
+            last_error: None,
+        });
+
+        f(task)
+    }
+}
+
+/// Returns `name` or, if it is already taken, `name#<n>` with the lowest free `n` starting from 2.
+fn unique_name(tasks: &BTreeMap<String, TaskInfo>, name: &str) -> String {
+    if !tasks.contains_key(name) {
+        return name.to_owned();
+    }
+
+    let unique = (2..)
+        .map(|n| format!("{name}#{n}"))
+        .find(|unique| !tasks.contains_key(unique))
+        .unwrap_or_else(|| name.to_owned());
+    warn!("Background task `{name}` is already registered, registering as `{unique}`.");
+    unique
+}
+
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod supervisor_test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn policy(restart: Restart, max_restarts: Option<usize>) -> RestartPolicy {
+        RestartPolicy {
+            restart,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(10),
+            max_restarts,
+        }
+    }
+
+    #[tokio::test]
+    async fn restarts_failed_task() {
+        let supervisor = Supervisor::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let counter = runs.clone();
+        supervisor.register("failing", policy(Restart::OnFailure, Some(3)), move |_| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>("boom")
+            }
+        });
+
+        supervisor.start();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let task = supervisor.tasks().remove("failing").unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 4);
+        assert_eq!(task.restarts, 3);
+        assert_eq!(task.state, TaskState::Failed);
+        assert_eq!(task.last_error.as_deref(), Some("boom"));
+        assert!(!supervisor.is_alive());
+    }
+
+    #[tokio::test]
+    async fn cancels_tasks_on_stop() {
+        let supervisor = Supervisor::default();
+
+        supervisor.register("loop", RestartPolicy::default(), |token| async move {
+            token.cancelled().await;
+            Ok::<_, String>(())
+        });
+
+        supervisor.start();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let state = |supervisor: &Supervisor| supervisor.tasks().get("loop").map(|task| task.state);
+        assert_eq!(state(&supervisor), Some(TaskState::Running));
+
+        supervisor.stop(Some(Duration::from_secs(1))).await;
+
+        assert_eq!(state(&supervisor), Some(TaskState::Stopped));
+        assert!(supervisor.is_alive());
+    }
+
+    #[tokio::test]
+    async fn duplicate_names_are_made_unique() {
+        let supervisor = Supervisor::default();
+
+        for _ in 0..3 {
+            supervisor.register("task", RestartPolicy::default(), |token| async move {
+                token.cancelled().await;
+                Ok::<_, String>(())
+            });
+        }
+
+        let names = supervisor.tasks().into_keys().collect::<Vec<_>>();
+        assert_eq!(names, ["task", "task#2", "task#3"]);
+    }
+
+    #[tokio::test]
+    async fn stop_timeout_is_shared_by_all_tasks() {
+        let supervisor = Supervisor::default();
+
+        for name in ["first", "second", "third"] {
+            supervisor.register(name, RestartPolicy::default(), |_| async move {
+                sleep(Duration::from_secs(60)).await;
+                Ok::<_, String>(())
+            });
+        }
+
+        supervisor.start();
+        let started = Instant::now();
+        supervisor.stop(Some(Duration::from_millis(100))).await;
+
+        assert!(started.elapsed() < Duration::from_millis(250));
+    }
+}
@@ -14,8 +14,9 @@ use futures_util::{
     stream::{FuturesUnordered, Stream},
     StreamExt, TryStreamExt,
 };
-use hyper::{server::accept, Server};
+use hyper::server::accept::{self, Accept};
 pub(crate) use reexport::*;
+use socket2::{SockRef, TcpKeepalive};
 use std::{sync::Arc, time::Duration};
 use tokio::{
     net::{TcpListener, TcpStream},
@@ -26,39 +27,55 @@ use tokio::{
 use tokio_stream::wrappers::TcpListenerStream;
 use tracing::{info, warn};
 
-use crate::application::shutdown_signal;
+use crate::application::limits::{ConnectionLimit, LimitedConn};
+use crate::application::server_builder;
+use crate::application::shutdown::Shutdown;
+use crate::configuration::HttpConfig;
 use crate::tls::TlsStream;
 use axum::extract::connect_info::Connected;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-pub(in crate::application) async fn run_service(
-    socket: &SocketAddr,
-    router: Router,
+pub(in crate::application) fn bind(
+    listener: std::net::TcpListener,
     tls_handshake_timeout: Duration,
     pem: Vec<u8>,
     key: Vec<u8>,
-) -> Result<()> {
+    http_cfg: HttpConfig,
+    limit: ConnectionLimit,
+) -> Result<impl Accept<Conn = LimitedConn<TlsStream>, Error = Error>> {
     let acceptor = create_acceptor(&pem, &key)?;
     drop((pem, key));
 
-    let stream = bind_tls_stream(socket, acceptor, tls_handshake_timeout).await?;
-    let incoming = accept::from_stream(stream);
-
-    let app = router.into_make_service_with_connect_info::<RemoteAddr>();
-    let server = Server::builder(incoming).serve(app);
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+    let socket = listener.local_addr()?;
 
+    let stream = tls_stream(listener, acceptor, tls_handshake_timeout, http_cfg, limit);
     info!(target: "server", "Started: https://{socket}");
 
-    Ok(server.with_graceful_shutdown(shutdown_signal()).await?)
+    Ok(accept::from_stream(stream))
+}
+
+pub(in crate::application) async fn run_service(
+    incoming: impl Accept<Conn = LimitedConn<TlsStream>, Error = Error>,
+    router: Router,
+    shutdown: &Shutdown,
+    http_cfg: &HttpConfig,
+) -> Result<()> {
+    let app = router.into_make_service_with_connect_info::<RemoteAddr>();
+    let server = server_builder(incoming, shutdown, http_cfg).serve(app);
+
+    Ok(server.with_graceful_shutdown(shutdown.subscribe()).await?)
 }
 
 #[allow(clippy::useless_conversion)]
-async fn bind_tls_stream(
-    socket: &SocketAddr,
+fn tls_stream(
+    listener: TcpListener,
     acceptor: TlsAcceptor,
     tls_handshake_timeout: Duration,
-) -> Result<impl Stream<Item = Result<TlsStream>>> {
-    let listener = TcpListener::bind(socket).await?;
+    http_cfg: HttpConfig,
+    limit: ConnectionLimit,
+) -> impl Stream<Item = Result<LimitedConn<TlsStream>>> {
     let mut tcp_stream = TcpListenerStream::new(listener);
 
     let acceptor = Arc::new(acceptor);
@@ -68,14 +85,22 @@ async fn bind_tls_stream(
         loop {
             match fetch_tls_handle_commands(&mut tcp_stream, &mut tasks).await {
                 Ok(TlsHandleCommands::TcpStream(tcp_stream)) => {
+                    if let Err(error) = configure_tcp(&tcp_stream, &http_cfg) {
+                        warn!("Failed to configure TCP connection: `{error}`.");
+                    }
+
+                    let Some(tcp_stream) = limit.try_acquire(tcp_stream) else {
+                        continue;
+                    };
+
                     let acceptor = acceptor.clone();
-                    tasks.push(tokio::task::spawn(async move {
+                    tasks.push(tokio::task::spawn(tcp_stream.upgrade(move |tcp_stream| async move {
                         let ret = timeout(tls_handshake_timeout, acceptor.accept(tcp_stream))
                             .await
                             .map_err(|_| Error::TlsHandshakeTimeout)??
                             .into();
                         Ok::<_, Error>(ret)
-                    }));
+                    })));
                 },
                 Ok(TlsHandleCommands::TlsStream(tls_stream)) => yield Ok(tls_stream),
                 Ok(TlsHandleCommands::Break) => break,
@@ -84,18 +109,29 @@ async fn bind_tls_stream(
         }
     };
 
-    Ok(ret)
+    ret
+}
+
+fn configure_tcp(tcp_stream: &TcpStream, http_cfg: &HttpConfig) -> std::io::Result<()> {
+    tcp_stream.set_nodelay(http_cfg.tcp_nodelay)?;
+
+    if let Some(keepalive) = http_cfg.tcp_keepalive {
+        let keepalive = TcpKeepalive::new().with_time(keepalive);
+        SockRef::from(tcp_stream).set_tcp_keepalive(&keepalive)?;
+    }
+
+    Ok(())
 }
 
 enum TlsHandleCommands {
     TcpStream(TcpStream),
-    TlsStream(TlsStream),
+    TlsStream(LimitedConn<TlsStream>),
     Break,
 }
 
 async fn fetch_tls_handle_commands(
     tcp_stream: &mut TcpListenerStream,
-    tasks: &mut FuturesUnordered<JoinHandle<Result<TlsStream>>>,
+    tasks: &mut FuturesUnordered<JoinHandle<Result<LimitedConn<TlsStream>>>>,
 ) -> Result<TlsHandleCommands> {
     let ret = if tasks.is_empty() {
         match tcp_stream.try_next().await? {
@@ -207,3 +243,9 @@ impl Connected<&TlsStream> for RemoteAddr {
         )
     }
 }
+
+impl Connected<&LimitedConn<TlsStream>> for RemoteAddr {
+    fn connect_info(target: &LimitedConn<TlsStream>) -> Self {
+        Self::connect_info(target.get_ref())
+    }
+}
@@ -1,6 +1,13 @@
 //! Trait to implement custom version response
 use crate::AppConfig;
 use axum::response::IntoResponse;
+use axum::Json;
+use metrics::{describe_gauge, gauge, Label};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const BUILD_INFO_METRIC: &str = "build_info";
 
 /// Trait to implement custom /version response
 pub trait VersionExt<PrivateCfg>: Send + Sync + 'static + Clone {
@@ -23,3 +30,240 @@ impl<T> VersionExt<T> for DefaultVersion {
         cfg.observability_cfg.version.clone()
     }
 }
+
+/// Build information captured at compile time, see [`crate::build_info`] macro.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    /// name of service crate.
+    pub crate_name: &'static str,
+    /// version of service crate.
+    pub crate_version: &'static str,
+    /// git commit service was built from, `GIT_COMMIT` compile time env variable.
+    pub git_commit: Option<&'static str>,
+    /// build timestamp, `BUILD_TIMESTAMP` compile time env variable.
+    pub build_timestamp: Option<&'static str>,
+    /// rustc version, `RUSTC_VERSION` compile time env variable.
+    pub rustc_version: Option<&'static str>,
+    /// enabled cargo features of service crate, comma separated `CARGO_FEATURES` compile time env variable.
+    pub features: Vec<&'static str>,
+    /// version of fregate service was built with.
+    pub fregate_version: &'static str,
+}
+
+impl BuildInfo {
+    /// Used by [`crate::build_info`] macro, prefer macro to calling it directly.
+    #[doc(hidden)]
+    pub fn new(
+        crate_name: &'static str,
+        crate_version: &'static str,
+        git_commit: Option<&'static str>,
+        build_timestamp: Option<&'static str>,
+        rustc_version: Option<&'static str>,
+        features: Option<&'static str>,
+    ) -> Self {
+        Self {
+            crate_name,
+            crate_version,
+            git_commit,
+            build_timestamp,
+            rustc_version,
+            features: features
+                .map(|features| features.split(',').filter(|f| !f.is_empty()).collect())
+                .unwrap_or_default(),
+            fregate_version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+
+    /// Prints `cargo:rustc-env` instructions for `GIT_COMMIT`, `BUILD_TIMESTAMP`, `RUSTC_VERSION` and `CARGO_FEATURES`.\
+    /// Supposed to be called from `build.rs` of service, values which could not be resolved are skipped.\
+    /// Build script is rerun when git `HEAD`, ref it points to or `packed-refs` change, i.e. on commit or checkout.
+    /// If git directory can't be resolved no `cargo:rerun-if-changed` is printed and cargo default rerun behaviour is kept.
+    ///
+    /// Example:
+    /// ```no_run
+    /// // in `fn main` of build.rs:
+    /// fregate::version::BuildInfo::emit_build_env();
+    /// ```
+    pub fn emit_build_env() {
+        if let Some(commit) = command_output("git", &["rev-parse", "HEAD"]) {
+            println!("cargo:rustc-env=GIT_COMMIT={commit}");
+        }
+
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+        if let Some(rustc_version) = command_output(&rustc, &["--version"]) {
+            println!("cargo:rustc-env=RUSTC_VERSION={rustc_version}");
+        }
+
+        let mut features = std::env::vars()
+            .filter_map(|(key, _)| {
+                key.strip_prefix("CARGO_FEATURE_")
+                    .map(|feature| feature.to_lowercase())
+            })
+            .collect::<Vec<_>>();
+        features.sort();
+
+        println!("cargo:rustc-env=CARGO_FEATURES={}", features.join(","));
+        println!(
+            "cargo:rustc-env=BUILD_TIMESTAMP={}",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        );
+        for path in git_watch_paths().unwrap_or_default() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+
+    /// Sets constant `build_info` gauge to `1` with build information as labels.
+    pub fn register_metric(&self) {
+        let labels = vec![
+            Label::new("crate_name", self.crate_name),
+            Label::new("crate_version", self.crate_version),
+            Label::new("git_commit", self.git_commit.unwrap_or_default()),
+            Label::new("build_timestamp", self.build_timestamp.unwrap_or_default()),
+            Label::new("rustc_version", self.rustc_version.unwrap_or_default()),
+            Label::new("features", self.features.join(",")),
+            Label::new("fregate_version", self.fregate_version),
+        ];
+
+        describe_gauge!(
+            BUILD_INFO_METRIC,
+            "Constant 1 with build information in labels."
+        );
+        gauge!(BUILD_INFO_METRIC, 1.0, labels);
+    }
+}
+
+/// Returns git files changed on commit or checkout: `HEAD`, ref it points to and `packed-refs`.\
+/// Returns [`None`] if git directory can't be resolved, so cargo keeps its default rerun behaviour.
+fn git_watch_paths() -> Option<Vec<PathBuf>> {
+    let git_dir = PathBuf::from(command_output("git", &["rev-parse", "--git-dir"])?);
+    let common_dir = command_output("git", &["rev-parse", "--git-common-dir"])
+        .map_or_else(|| git_dir.clone(), PathBuf::from);
+
+    let mut paths = vec![git_dir.join("HEAD")];
+    if let Some(head_ref) = command_output("git", &["symbolic-ref", "-q", "HEAD"]) {
+        paths.push(common_dir.join(head_ref));
+    }
+    paths.push(common_dir.join("packed-refs"));
+
+    // cargo reruns build script on every build if watched file doesn't exist.
+    paths.retain(|path| path.exists());
+    Some(paths)
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|output| output.trim().to_owned())
+}
+
+/// Captures [`BuildInfo`] of the crate where macro is called.\
+/// Crate name and version are taken from cargo, other values from compile time env variables
+/// which might be set with [`BuildInfo::emit_build_env`] in `build.rs`.
+///
+/// Example:
+/// ```rust
+/// use fregate::build_info;
+///
+/// let info = build_info!();
+/// assert_eq!(info.crate_version, env!("CARGO_PKG_VERSION"));
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::version::BuildInfo::new(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            option_env!("GIT_COMMIT"),
+            option_env!("BUILD_TIMESTAMP"),
+            option_env!("RUSTC_VERSION"),
+            option_env!("CARGO_FEATURES"),
+        )
+    };
+}
+
+/// Returns [`BuildInfo`] as json and exports it as `build_info` metric.
+///
+/// Example:
+/// ```no_run
+/// use fregate::version::BuildInfoVersion;
+/// use fregate::{bootstrap, build_info, tokio, AppConfig, Application};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config: AppConfig = bootstrap([]).unwrap();
+///
+///     Application::new(config)
+///         .version(BuildInfoVersion::new(build_info!()))
+///         .serve()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BuildInfoVersion(Arc<BuildInfo>);
+
+impl BuildInfoVersion {
+    /// Creates version and sets `build_info` metric, so should be called after metrics are initialised, e.g. with [`crate::bootstrap`].
+    pub fn new(info: BuildInfo) -> Self {
+        info.register_metric();
+        Self(Arc::new(info))
+    }
+
+    /// Returns captured [`BuildInfo`].
+    pub fn info(&self) -> &BuildInfo {
+        &self.0
+    }
+}
+
+impl<T> VersionExt<T> for BuildInfoVersion {
+    type Response = Json<BuildInfo>;
+
+    fn get_version(&self, _cfg: &AppConfig<T>) -> Self::Response {
+        Json(self.0.as_ref().clone())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod version_test {
+    use super::*;
+
+    #[test]
+    fn build_info_macro_captures_crate() {
+        let info = crate::build_info!();
+
+        assert_eq!(info.crate_name, "fregate");
+        assert_eq!(info.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.fregate_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn build_info_version_json() {
+        let info = BuildInfo::new("svc", "1.2.3", Some("abc"), None, None, Some("a,b"));
+        let version = BuildInfoVersion::new(info);
+
+        let Json(json) = VersionExt::<crate::Empty>::get_version(&version, &AppConfig::default());
+        let json = serde_json::to_value(json).unwrap();
+
+        assert_eq!(json["crate_name"], "svc");
+        assert_eq!(json["git_commit"], "abc");
+        assert_eq!(json["build_timestamp"], serde_json::Value::Null);
+        assert_eq!(json["features"], serde_json::json!(["a", "b"]));
+    }
+
+    #[test]
+    fn git_watch_paths_exist() {
+        let Some(paths) = git_watch_paths() else {
+            return;
+        };
+
+        assert!(paths.iter().any(|path| path.ends_with("HEAD")));
+        assert!(paths.iter().all(|path| path.exists()));
+    }
+}
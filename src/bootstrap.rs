@@ -1,14 +1,10 @@
 //!This is a shortcut fn to read [`AppConfig`] and call [`init_tracing`] and [`init_metrics`] fn.
-use crate::observability::cgroupv2::init_cgroup_metrics;
-use crate::observability::sys_info::init_sys_metrics;
-#[cfg(feature = "tokio-metrics")]
-use crate::observability::tokio_metrics::init_tokio_metrics_task;
 use crate::observability::{init_metrics, init_tracing};
 use crate::{error::Result, *};
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
-/// Reads AppConfig and calls [`init_tracing`].
+/// Reads AppConfig same way as [`AppConfig::load_from`], including files of active profile, and calls [`init_tracing`].
 /// Return Error if fails to read [`AppConfig`] or [`init_tracing`] returns error.
 /// Return Error if called twice because of internal call to [`tracing_subscriber::registry().try_init()`].
 ///```no_run
@@ -80,7 +76,37 @@ where
     S: IntoIterator<Item = ConfigSource<'a>>,
     ConfigExt: Debug + DeserializeOwned,
 {
-    let mut config = AppConfig::<ConfigExt>::load_from(sources)?;
+    bootstrap_with_builder(AppConfigBuilder::from_sources(sources), callback)
+}
+
+/// This has same functionalilty as [`bootstrap_with_callback`] function, but reads [`AppConfig`]
+/// from given [`AppConfigBuilder`], e.g. to turn on strict validation.
+/// Example:
+///```no_run
+/// use fregate::*;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let builder = AppConfig::<Empty>::builder()
+///         .add_default()
+///         .add_env_prefixed("OTEL")
+///         .add_file("./examples/configuration/app.yaml")
+///         .strict(true)
+///         .validate();
+///
+///     let config = bootstrap_with_builder(builder, |_cfg| {}).unwrap();
+///
+///     Application::new(config).serve().await.unwrap();
+/// }
+/// ```
+pub fn bootstrap_with_builder<ConfigExt>(
+    builder: AppConfigBuilder<ConfigExt>,
+    callback: impl FnOnce(&mut AppConfig<ConfigExt>),
+) -> Result<AppConfig<ConfigExt>>
+where
+    ConfigExt: Debug + DeserializeOwned,
+{
+    let mut config = builder.build()?;
     callback(&mut config);
     let ObservabilityConfig {
         service_name,
@@ -88,7 +114,7 @@ where
         version,
         logger_config,
         cgroup_metrics,
-        metrics_update_interval,
+        metrics_update_interval: _,
         trace_level,
         traces_endpoint,
     } = &config.observability_cfg;
@@ -105,15 +131,13 @@ where
     config.worker_guard.replace(worker_guard);
     init_metrics(*cgroup_metrics)?;
 
-    #[cfg(feature = "tokio-metrics")]
-    init_tokio_metrics_task(*metrics_update_interval);
-
-    if *cgroup_metrics {
-        init_cgroup_metrics(*metrics_update_interval)
-    } else {
-        init_sys_metrics(*metrics_update_interval);
-    }
-
-    tracing::info!("Configuration: `{config:?}`.");
+    tracing::info!(
+        "Configuration sources: `{:?}`.",
+        config.effective_cfg.layers()
+    );
+    let masked = config
+        .effective_cfg
+        .masked(&config.management_cfg.config_dump.sanitize);
+    tracing::info!("Configuration: `{masked}`.");
     Ok(config)
 }
@@ -1,17 +1,44 @@
 //! See in [`examples`](https://github.com/elefant-dev/fregate-rs/blob/main/examples/configuration/src/main.rs) how to configure your [`crate::Application`]
 mod application;
+mod args;
+mod effective;
 mod observability;
+mod placeholder;
+mod profile;
+mod schema;
 mod source;
+pub(crate) mod units;
+mod validation;
+mod watcher;
 
+mod http;
+mod limits;
 mod management;
+mod shutdown;
 #[cfg(feature = "tls")]
 mod tls;
 
 #[doc(inline)]
 pub use application::*;
 #[doc(inline)]
+pub use effective::*;
+#[doc(inline)]
+pub use http::*;
+#[doc(inline)]
+pub use limits::*;
+#[doc(inline)]
 pub use management::*;
 #[doc(inline)]
 pub use observability::*;
 #[doc(inline)]
+pub use profile::{CONFIG_DIR_ENV, PROFILE_ENV};
+#[doc(inline)]
+pub use schema::*;
+#[doc(inline)]
+pub use shutdown::*;
+#[doc(inline)]
 pub use source::*;
+#[doc(inline)]
+pub use validation::*;
+#[doc(inline)]
+pub use watcher::*;
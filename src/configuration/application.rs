@@ -1,13 +1,21 @@
+use crate::configuration::args::Args;
 use crate::configuration::observability::ObservabilityConfig;
+use crate::configuration::placeholder::resolve_placeholders;
+use crate::configuration::profile::{active_profile, profile_files};
 use crate::configuration::source::ConfigSource;
-use crate::{error::Result, extensions::DeserializeExt, ManagementConfig};
-use config::{builder::DefaultState, ConfigBuilder, Environment, File, FileFormat};
+use crate::configuration::validation::validate_strict;
+use crate::{
+    error::Result, extensions::DeserializeExt, EffectiveConfig, HttpConfig, LimitsConfig,
+    ManagementConfig, ShutdownConfig, Validate, ValidationErrors,
+};
+use config::{builder::DefaultState, Config, ConfigBuilder, Environment, File, FileFormat};
 use serde::{
     de::{DeserializeOwned, Error},
     Deserialize, Deserializer,
 };
 use serde_json::Value;
 use std::marker::PhantomData;
+use std::path::Path;
 use std::{fmt::Debug, net::IpAddr};
 use tracing_appender::non_blocking::WorkerGuard;
 
@@ -17,7 +25,10 @@ use crate::configuration::tls::TlsConfigurationVariables;
 const HOST_PTR: &str = "/host";
 const PORT_PTR: &str = "/port";
 const PORT_SERVER_PTR: &str = "/server/port";
+const SERVER_HTTP_PTR: &str = "/server/http";
+const SERVER_LIMITS_PTR: &str = "/server/limits";
 const MANAGEMENT_PTR: &str = "/management";
+const SHUTDOWN_PTR: &str = "/shutdown";
 
 const DEFAULT_CONFIG: &str = include_str!("../resources/default_conf.toml");
 const DEFAULT_SEPARATOR: &str = "_";
@@ -34,15 +45,23 @@ pub struct AppConfig<ConfigExt = Empty> {
     /// When serialized uses `<PREFIX>`_PORT or `<PREFIX>`_SERVER_PORT names.
     /// `<PREFIX>`_SERVER_PORT has higher priority.
     pub port: u16,
+    /// configures HTTP server
+    pub http_cfg: HttpConfig,
+    /// configures connection and concurrency limits
+    pub limits_cfg: LimitsConfig,
     /// configuration for logs and traces
     pub observability_cfg: ObservabilityConfig,
     /// configures management endpoints
     pub management_cfg: ManagementConfig,
+    /// configures graceful shutdown
+    pub shutdown_cfg: ShutdownConfig,
     /// TLS configuration parameters
     #[cfg(feature = "tls")]
     pub tls: TlsConfigurationVariables,
     /// field for each application specific configuration
     pub private: ConfigExt,
+    /// merged values and their sources, filled by [`AppConfigBuilder::build`]
+    pub effective_cfg: EffectiveConfig,
     /// Why it is here read more: [`https://docs.rs/tracing-appender/latest/tracing_appender/non_blocking/struct.WorkerGuard.html`]
     /// This one will not be cloned and will be set to [`None`] in clone.
     pub worker_guard: Option<WorkerGuard>,
@@ -56,11 +75,15 @@ where
         Self {
             host: self.host,
             port: self.port,
+            http_cfg: self.http_cfg,
+            limits_cfg: self.limits_cfg,
             observability_cfg: self.observability_cfg.clone(),
             management_cfg: self.management_cfg.clone(),
+            shutdown_cfg: self.shutdown_cfg.clone(),
             #[cfg(feature = "tls")]
             tls: self.tls.clone(),
             private: self.private.clone(),
+            effective_cfg: self.effective_cfg.clone(),
             worker_guard: None,
         }
     }
@@ -80,8 +103,17 @@ where
             .pointer_and_deserialize(PORT_SERVER_PTR)
             .or_else(|_err: D::Error| config.pointer_and_deserialize(PORT_PTR))?;
 
+        let http_cfg = config
+            .pointer_and_deserialize_optional::<_, D::Error>(SERVER_HTTP_PTR)?
+            .unwrap_or_default();
+        let limits_cfg = config
+            .pointer_and_deserialize_optional::<_, D::Error>(SERVER_LIMITS_PTR)?
+            .unwrap_or_default();
         let management_cfg = config
-            .pointer_and_deserialize::<_, D::Error>(MANAGEMENT_PTR)
+            .pointer_and_deserialize_optional::<_, D::Error>(MANAGEMENT_PTR)?
+            .unwrap_or_default();
+        let shutdown_cfg = config
+            .pointer_and_deserialize_optional::<_, D::Error>(SHUTDOWN_PTR)?
             .unwrap_or_default();
         let observability_cfg = ObservabilityConfig::deserialize(&config).map_err(Error::custom)?;
         #[cfg(feature = "tls")]
@@ -91,11 +123,15 @@ where
         Ok(AppConfig::<ConfigExt> {
             host,
             port,
+            http_cfg,
+            limits_cfg,
             observability_cfg,
             management_cfg,
+            shutdown_cfg,
             #[cfg(feature = "tls")]
             tls,
             private,
+            effective_cfg: Default::default(),
             worker_guard: None,
         })
     }
@@ -133,25 +169,14 @@ impl<ConfigExt> AppConfig<ConfigExt> {
             .build()
     }
 
-    /// Load configuration from provided container with [`ConfigSource`] which override default config.
+    /// Load configuration from provided container with [`ConfigSource`] which override default config.\
+    /// Files of active profile are added before provided sources, see [`AppConfigBuilder::add_active_profile`].
     pub fn load_from<'a, S>(sources: S) -> Result<Self>
     where
         ConfigExt: Debug + DeserializeOwned,
         S: IntoIterator<Item = ConfigSource<'a>>,
     {
-        let mut config_builder = AppConfig::<ConfigExt>::builder()
-            .add_default()
-            .add_env_prefixed("OTEL");
-
-        for source in sources {
-            config_builder = match source {
-                ConfigSource::String(str, format) => config_builder.add_str(str, format),
-                ConfigSource::File(path) => config_builder.add_file(path),
-                ConfigSource::EnvPrefix(prefix) => config_builder.add_env_prefixed(prefix),
-            };
-        }
-
-        config_builder.build()
+        AppConfigBuilder::from_sources(sources).build()
     }
 }
 
@@ -159,6 +184,10 @@ impl<ConfigExt> AppConfig<ConfigExt> {
 #[derive(Debug, Default)]
 pub struct AppConfigBuilder<ConfigExt> {
     builder: ConfigBuilder<DefaultState>,
+    // each source separately with its label, used to find out where values came from.
+    layers: Vec<(String, ConfigBuilder<DefaultState>)>,
+    strict: bool,
+    validator: Option<fn(&ConfigExt, &mut ValidationErrors)>,
     phantom: PhantomData<ConfigExt>,
 }
 
@@ -167,52 +196,216 @@ impl<ConfigExt> AppConfigBuilder<ConfigExt> {
     pub fn new() -> Self {
         Self {
             builder: ConfigBuilder::default(),
+            layers: Vec::new(),
+            strict: false,
+            validator: None,
             phantom: PhantomData,
         }
     }
 
-    /// Reads all registered sources
+    /// Adds default config, `OTEL` prefixed env variables, files of active profile and given sources in this order,
+    /// shared by [`AppConfig::load_from`], [`crate::bootstrap`] and [`crate::ConfigWatcher`].
+    pub(crate) fn from_sources<'a, S>(sources: S) -> Self
+    where
+        S: IntoIterator<Item = ConfigSource<'a>>,
+    {
+        Self::new()
+            .add_default()
+            .add_env_prefixed("OTEL")
+            .add_active_profile()
+            .add_sources(sources)
+    }
+
+    /// In strict mode values of built-in sections which would silently fall back to defaults
+    /// (invalid endpoints, unparsable `log.level`, out of range numbers, etc.) are reported as errors.
+    /// All found problems are returned at once in [`crate::error::Error::ValidationError`].
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Calls [`Validate::validate`] on `private` section after it is deserialized.
+    #[must_use]
+    pub fn validate(mut self) -> Self
+    where
+        ConfigExt: Validate,
+    {
+        self.validator = Some(ConfigExt::validate);
+        self
+    }
+
+    /// Reads all registered sources.\
+    /// `${file:/path}` and `${env:NAME}` placeholders in string values are replaced with file content
+    /// (without trailing newlines) or env variable value, value consisting of single placeholder is parsed
+    /// same way as environment variables. Such values are considered sensitive
+    /// and masked in `Configuration:` log line and [`EffectiveConfig::to_json`].
     pub fn build(self) -> Result<AppConfig<ConfigExt>>
     where
         ConfigExt: Debug + DeserializeOwned,
     {
-        Ok(self
-            .builder
-            .build()?
-            .try_deserialize::<AppConfig<ConfigExt>>()?)
+        let mut config = self.builder.build()?;
+        let mut value = config.clone().try_deserialize::<Value>()?;
+
+        let mut errors = ValidationErrors::default();
+        let sensitive = resolve_placeholders(&mut value, &mut errors);
+        if !sensitive.is_empty() {
+            config = Config::try_from(&value)?;
+        }
+
+        let layers = self
+            .layers
+            .into_iter()
+            .map(|(source, layer)| Ok((source, layer.build()?.try_deserialize::<Value>()?)))
+            .collect::<Result<Vec<_>>>()?;
+        let effective_cfg = EffectiveConfig::new(value, layers).with_sensitive(sensitive);
+
+        if self.strict {
+            validate_strict(effective_cfg.value(), &mut errors);
+        }
+        if !errors.is_empty() {
+            return Err(errors.with_sources(&effective_cfg).into());
+        }
+
+        let mut app_config = config.try_deserialize::<AppConfig<ConfigExt>>()?;
+        if let Some(validator) = self.validator {
+            validator(&app_config.private, &mut errors);
+        }
+        if !errors.is_empty() {
+            return Err(errors.with_sources(&effective_cfg).into());
+        }
+
+        app_config.effective_cfg = effective_cfg;
+        Ok(app_config)
     }
 
-    /// Add default config
+    /// Add sources in given order, [`ConfigSource::Args`] is added last to have highest precedence.
     #[must_use]
-    pub fn add_default(mut self) -> Self {
-        self.builder = self
-            .builder
-            .add_source(File::from_str(DEFAULT_CONFIG, FileFormat::Toml));
+    pub fn add_sources<'a, S>(mut self, sources: S) -> Self
+    where
+        S: IntoIterator<Item = ConfigSource<'a>>,
+    {
+        let mut add_args = false;
+        for source in sources {
+            self = match source {
+                ConfigSource::String(str, format) => self.add_str(str, format),
+                ConfigSource::File(path) => self.add_file(path),
+                ConfigSource::EnvPrefix(prefix) => self.add_env_prefixed(prefix),
+                ConfigSource::Args => {
+                    add_args = true;
+                    self
+                }
+            };
+        }
+
+        if add_args {
+            self.add_args()
+        } else {
+            self
+        }
+    }
+
+    fn add_source<S>(mut self, label: String, source: S) -> Self
+    where
+        S: config::Source + Clone + Send + Sync + 'static,
+    {
+        self.layers.push((
+            label,
+            ConfigBuilder::<DefaultState>::default().add_source(source.clone()),
+        ));
+        self.builder = self.builder.add_source(source);
         self
     }
 
+    /// Add default config
+    #[must_use]
+    pub fn add_default(self) -> Self {
+        self.add_source(
+            "default".to_owned(),
+            File::from_str(DEFAULT_CONFIG, FileFormat::Toml),
+        )
+    }
+
     /// Add file
     #[must_use]
-    pub fn add_file(mut self, path: &str) -> Self {
-        self.builder = self.builder.add_source(File::with_name(path));
+    pub fn add_file(self, path: &str) -> Self {
+        self.add_source(format!("file:{path}"), File::with_name(path))
+    }
+
+    /// Add `app.yaml`, `app-<profile>.yaml` and optional `app-local.yaml` files from `dir` in given order.
+    /// Missing `app-local.yaml` is skipped, other missing files make [`AppConfigBuilder::build`] fail.
+    #[must_use]
+    pub fn add_profile(mut self, dir: &str, profile: &str) -> Self {
+        for (path, required) in profile_files(dir, profile) {
+            if required || Path::new(&path).is_file() {
+                self = self.add_file(&path);
+            }
+        }
         self
     }
 
+    /// Calls [`AppConfigBuilder::add_profile`] with profile from [`crate::PROFILE_ENV`] env variable
+    /// and directory from [`crate::CONFIG_DIR_ENV`] env variable (current directory by default).\
+    /// Nothing is added if [`crate::PROFILE_ENV`] is not set.
+    #[must_use]
+    pub fn add_active_profile(self) -> Self {
+        match active_profile() {
+            Some((dir, profile)) => self.add_profile(&dir, &profile),
+            None => self,
+        }
+    }
+
     /// Add string
     #[must_use]
-    pub fn add_str(mut self, str: &str, format: FileFormat) -> Self {
-        self.builder = self.builder.add_source(File::from_str(str, format));
-        self
+    pub fn add_str(self, str: &str, format: FileFormat) -> Self {
+        self.add_source("string".to_owned(), File::from_str(str, format))
+    }
+
+    /// Add command-line arguments of current process, see [`AppConfigBuilder::add_args_from`].
+    #[must_use]
+    pub fn add_args(self) -> Self {
+        self.add_args_from(std::env::args().skip(1))
+    }
+
+    /// Add files given with `--config <file>` or `--config=<file>` and then values given with `--key.path=value` or `--key.path value`.\
+    /// Key path is split by `.` into the same keys [`AppConfig`] is read from,
+    /// e.g. `--server.port=9000` or `--management.auth.token=secret`,
+    /// values are parsed same way as environment variables.
+    /// Arguments after `--` and any other arguments are ignored.
+    #[must_use]
+    pub fn add_args_from<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let Args {
+            config_files,
+            overrides,
+        } = Args::parse(args);
+
+        for path in config_files {
+            self = self.add_file(&path);
+        }
+
+        if overrides.is_empty() {
+            self
+        } else {
+            let overrides = Value::Object(overrides).to_string();
+            self.add_source(
+                "args".to_owned(),
+                File::from_str(&overrides, FileFormat::Json),
+            )
+        }
     }
 
     /// Add environment variables with specified prefix and default separator: "_"
     #[must_use]
-    pub fn add_env_prefixed(mut self, prefix: &str) -> Self {
-        self.builder = self.builder.add_source(
+    pub fn add_env_prefixed(self, prefix: &str) -> Self {
+        self.add_source(
+            format!("env:{prefix}"),
             Environment::with_prefix(prefix)
                 .try_parsing(true)
                 .separator(DEFAULT_SEPARATOR),
-        );
-        self
+        )
     }
 }
@@ -0,0 +1,137 @@
+use serde_json::{Map, Value};
+
+const CONFIG_ARG: &str = "--config";
+const ARG_PREFIX: &str = "--";
+const KEY_SEPARATOR: char = '.';
+
+/// Configuration related command-line arguments.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Args {
+    /// paths given with `--config <file>` or `--config=<file>` in given order.
+    pub(crate) config_files: Vec<String>,
+    /// values given with `--key.path=value` or `--key.path value`, nested by `.` same way as JSON pointers of [`crate::AppConfig`].
+    pub(crate) overrides: Map<String, Value>,
+}
+
+impl Args {
+    /// Parses arguments without program name, everything after `--` and not recognised arguments are ignored.\
+    /// Argument following `--key.path` is taken as its value unless it starts with `--`, so flags without value are ignored.
+    pub(crate) fn parse<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter().map(Into::into).peekable();
+
+        while let Some(arg) = args.next() {
+            if arg == ARG_PREFIX {
+                break;
+            } else if arg == CONFIG_ARG {
+                parsed.config_files.extend(args.next());
+            } else if let Some(path) = arg
+                .strip_prefix(CONFIG_ARG)
+                .and_then(|s| s.strip_prefix('='))
+            {
+                parsed.config_files.push(path.to_owned());
+            } else if let Some(key) = arg.strip_prefix(ARG_PREFIX) {
+                match key.split_once('=') {
+                    Some((key, value)) => parsed.insert(key, value),
+                    None => {
+                        if let Some(value) = args.next_if(|next| !next.starts_with(ARG_PREFIX)) {
+                            parsed.insert(key, &value);
+                        }
+                    }
+                }
+            }
+        }
+
+        parsed
+    }
+
+    /// Returns paths of `--config` files.
+    pub(crate) fn config_files() -> Vec<String> {
+        Args::parse(std::env::args().skip(1)).config_files
+    }
+
+    fn insert(&mut self, key: &str, value: &str) {
+        let segments = key.split(KEY_SEPARATOR).collect::<Vec<_>>();
+        let Some((last, path)) = segments.split_last() else {
+            return;
+        };
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return;
+        }
+
+        let mut map = &mut self.overrides;
+        for segment in path {
+            let entry = map
+                .entry(segment.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                *entry = Value::Object(Map::new());
+            }
+            let Value::Object(nested) = entry else {
+                return;
+            };
+            map = nested;
+        }
+        map.insert(last.to_string(), parse_value(value));
+    }
+}
+
+/// Parses value same way as environment variables are parsed: boolean, integer, float or string.
+pub(crate) fn parse_value(value: &str) -> Value {
+    if let Ok(value) = value.to_lowercase().parse::<bool>() {
+        Value::from(value)
+    } else if let Ok(value) = value.parse::<i64>() {
+        Value::from(value)
+    } else if let Some(value) = value
+        .parse::<f64>()
+        .ok()
+        .and_then(serde_json::Number::from_f64)
+    {
+        Value::Number(value)
+    } else {
+        Value::from(value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod args_test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_config_files_and_overrides() {
+        let args = Args::parse([
+            "serve",
+            "--config",
+            "./app.yaml",
+            "--config=./local.yaml",
+            "--server.port=9000",
+            "--server.host",
+            "127.0.0.1",
+            "--log.level=debug,hyper=info",
+            "--management.auth.exempt.probes=false",
+            "--verbose",
+            "--.port=1",
+            "--trace.level",
+            "warn",
+            "--",
+            "--port=1",
+        ]);
+
+        assert_eq!(args.config_files, ["./app.yaml", "./local.yaml"]);
+        assert_eq!(
+            Value::Object(args.overrides),
+            json!({
+                "server": {"port": 9000, "host": "127.0.0.1"},
+                "log": {"level": "debug,hyper=info"},
+                "trace": {"level": "warn"},
+                "management": {"auth": {"exempt": {"probes": false}}},
+            })
+        );
+    }
+}
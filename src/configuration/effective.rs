@@ -0,0 +1,250 @@
+use crate::observability::{Filter, SANITIZED_VALUE};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Source label used for values which were not found in any registered source.
+const UNKNOWN_SOURCE: &str = "unknown";
+
+/// Merged configuration values [`crate::AppConfig`] was deserialized from,
+/// together with source of each value, e.g. `default`, `file:./app.yaml` or `env:OTEL`.\
+/// Might be exposed with management endpoint, see [`crate::ConfigDumpConfig`].
+#[derive(Clone, Default)]
+pub struct EffectiveConfig {
+    value: Arc<Value>,
+    sources: Arc<BTreeMap<String, String>>,
+    layers: Arc<Vec<String>>,
+    sensitive: Arc<BTreeSet<String>>,
+}
+
+impl Debug for EffectiveConfig {
+    // values are not printed on purpose as they might contain secrets.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EffectiveConfig")
+            .field("values", &self.sources.len())
+            .finish()
+    }
+}
+
+impl EffectiveConfig {
+    /// Creates [`EffectiveConfig`] from merged value and values of each source in order of precedence.
+    pub(crate) fn new(value: Value, layers: impl IntoIterator<Item = (String, Value)>) -> Self {
+        let mut sources = BTreeMap::new();
+        let mut labels = Vec::new();
+
+        for (source, layer) in layers {
+            visit_leaves(&layer, String::new(), &mut |pointer, _| {
+                sources.insert(pointer, source.clone());
+            });
+            labels.push(source);
+        }
+
+        Self {
+            value: Arc::new(value),
+            sources: Arc::new(sources),
+            layers: Arc::new(labels),
+            sensitive: Default::default(),
+        }
+    }
+
+    /// Returns sources in order of precedence, e.g. `["default", "env:OTEL", "file:./app.yaml"]`.
+    pub fn layers(&self) -> &[String] {
+        &self.layers
+    }
+
+    /// Marks values by json pointers as sensitive, those are always masked.
+    pub(crate) fn with_sensitive(mut self, pointers: impl IntoIterator<Item = String>) -> Self {
+        self.sensitive = Arc::new(pointers.into_iter().collect());
+        self
+    }
+
+    /// Returns true if value by json pointer is sensitive, e.g. resolved from `${file:/path}` placeholder.
+    pub fn is_sensitive(&self, pointer: &str) -> bool {
+        self.sensitive.contains(pointer)
+    }
+
+    /// Returns true if value by json pointer or any of its elements is sensitive.
+    fn contains_sensitive(&self, pointer: &str) -> bool {
+        self.sensitive.iter().any(|sensitive| {
+            sensitive
+                .strip_prefix(pointer)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    }
+
+    /// Returns merged configuration where values under keys containing any of `sanitize` entries
+    /// and sensitive values are replaced with [`SANITIZED_VALUE`].
+    pub(crate) fn masked(&self, sanitize: &Filter) -> Value {
+        self.mask(&self.value, String::new(), sanitize, false)
+    }
+
+    fn mask(&self, value: &Value, pointer: String, sanitize: &Filter, masked: bool) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| {
+                        let masked = masked || is_sensitive(key, sanitize);
+                        let pointer = format!("{pointer}/{}", escape(key));
+                        (key.clone(), self.mask(value, pointer, sanitize, masked))
+                    })
+                    .collect::<Map<_, _>>(),
+            ),
+            Value::Array(array) => Value::Array(
+                array
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, value)| {
+                        self.mask(value, format!("{pointer}/{idx}"), sanitize, masked)
+                    })
+                    .collect(),
+            ),
+            _ if masked || self.is_sensitive(&pointer) => Value::from(SANITIZED_VALUE),
+            leaf => leaf.clone(),
+        }
+    }
+
+    /// Returns merged configuration value.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Returns source of value by json pointer, e.g. `/server/port`.
+    pub fn source(&self, pointer: &str) -> Option<&str> {
+        self.sources.get(pointer).map(String::as_str)
+    }
+
+    /// Returns configuration where each value is replaced with `{"value": .., "source": ..}` object.\
+    /// Values under keys containing any of `sanitize` entries and sensitive values are replaced with [`SANITIZED_VALUE`].
+    pub fn to_json(&self, sanitize: &Filter) -> Value {
+        self.describe(&self.value, String::new(), sanitize, false)
+    }
+
+    fn describe(&self, value: &Value, pointer: String, sanitize: &Filter, masked: bool) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, value)| {
+                        let masked = masked || is_sensitive(key, sanitize);
+                        let pointer = format!("{pointer}/{}", escape(key));
+                        (key.clone(), self.describe(value, pointer, sanitize, masked))
+                    })
+                    .collect::<Map<_, _>>(),
+            ),
+            leaf => {
+                let source = self.source(&pointer).unwrap_or(UNKNOWN_SOURCE);
+                let value = if masked || self.contains_sensitive(&pointer) {
+                    Value::from(SANITIZED_VALUE)
+                } else {
+                    leaf.clone()
+                };
+
+                serde_json::json!({ "value": value, "source": source })
+            }
+        }
+    }
+}
+
+fn is_sensitive(key: &str, sanitize: &Filter) -> bool {
+    match sanitize {
+        Filter::All => true,
+        Filter::Set(set) => {
+            let key = key.to_ascii_lowercase();
+            set.iter()
+                .any(|entry| !entry.is_empty() && key.contains(entry.as_str()))
+        }
+    }
+}
+
+fn visit_leaves(value: &Value, pointer: String, f: &mut impl FnMut(String, &Value)) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                visit_leaves(value, format!("{pointer}/{}", escape(key)), f);
+            }
+        }
+        leaf => f(pointer, leaf),
+    }
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::indexing_slicing)]
+mod effective_test {
+    use super::*;
+    use crate::observability::from_str_to_filter;
+    use serde_json::json;
+
+    #[test]
+    fn tracks_sources_and_masks_secrets() {
+        let default = json!({"port": 8000, "db": {"password": "default"}});
+        let env = json!({"port": 9000, "db": {"password": "secret"}});
+        let merged = json!({"port": 9000, "host": "0.0.0.0", "db": {"password": "secret"}});
+
+        let config = EffectiveConfig::new(
+            merged,
+            [("default".to_owned(), default), ("env:APP".to_owned(), env)],
+        )
+        .with_sensitive(["/host".to_owned()]);
+        assert_eq!(config.source("/port"), Some("env:APP"));
+        assert_eq!(config.layers(), ["default", "env:APP"]);
+
+        let json = config.to_json(&from_str_to_filter(Some("password".to_owned())));
+        assert_eq!(json["port"], json!({"value": 9000, "source": "env:APP"}));
+        assert_eq!(
+            json["host"],
+            json!({"value": SANITIZED_VALUE, "source": "unknown"})
+        );
+        assert_eq!(
+            json["db"]["password"],
+            json!({"value": SANITIZED_VALUE, "source": "env:APP"})
+        );
+    }
+
+    #[test]
+    fn masks_value_tree() {
+        let merged = json!({
+            "port": 9000,
+            "host": "0.0.0.0",
+            "db": {"password": "secret", "user": "admin"},
+            "hosts": ["first", "second"],
+        });
+
+        let config = EffectiveConfig::new(merged, [])
+            .with_sensitive(["/host".to_owned(), "/hosts/1".to_owned()]);
+        let masked = config.masked(&from_str_to_filter(Some("password".to_owned())));
+        assert_eq!(
+            masked,
+            json!({
+                "port": 9000,
+                "host": SANITIZED_VALUE,
+                "db": {"password": SANITIZED_VALUE, "user": "admin"},
+                "hosts": ["first", SANITIZED_VALUE],
+            })
+        );
+
+        let json = config.to_json(&Filter::Set(Default::default()));
+        assert_eq!(json["hosts"]["value"], json!(SANITIZED_VALUE));
+    }
+
+    #[test]
+    fn default_sanitize_keeps_key_paths() {
+        let merged = json!({
+            "server": {"tls": {"key": {"path": "/tls.key"}}},
+            "client": {"api_key": "secret", "private_key": "secret"},
+        });
+
+        let config = EffectiveConfig::new(merged, []);
+        let masked = config.masked(&crate::ConfigDumpConfig::default().sanitize);
+        assert_eq!(
+            masked,
+            json!({
+                "server": {"tls": {"key": {"path": "/tls.key"}}},
+                "client": {"api_key": SANITIZED_VALUE, "private_key": SANITIZED_VALUE},
+            })
+        );
+    }
+}
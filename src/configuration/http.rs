@@ -0,0 +1,82 @@
+use crate::configuration::units::Millis;
+use crate::extensions::DeserializeExt;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::time::Duration;
+
+const HTTP1_ONLY_PTR: &str = "/http1/only";
+const HTTP2_ONLY_PTR: &str = "/http2/only";
+const HTTP1_HEADER_READ_TIMEOUT_PTR: &str = "/http1/header/read/timeout";
+const HTTP2_KEEP_ALIVE_INTERVAL_PTR: &str = "/http2/keep/alive/interval";
+const HTTP2_KEEP_ALIVE_TIMEOUT_PTR: &str = "/http2/keep/alive/timeout";
+const HTTP2_MAX_CONCURRENT_STREAMS_PTR: &str = "/http2/max/concurrent/streams";
+const TCP_NODELAY_PTR: &str = "/tcp/nodelay";
+const TCP_KEEPALIVE_PTR: &str = "/tcp/keepalive";
+
+/// Tuning of HTTP server, applied to both plain and TLS servers.
+/// Hyper defaults are used for every option which is not set, invalid values fail config load.\
+/// Durations are read from number of milliseconds or string like `30s`.
+/// ```toml
+/// [server.http]
+/// http1.only = false
+/// http2.only = false
+/// http1.header.read.timeout = 30000 # in milliseconds
+/// http2.keep.alive.interval = 20000 # in milliseconds
+/// http2.keep.alive.timeout = "20s"
+/// http2.max.concurrent.streams = 200
+/// tcp.nodelay = true
+/// tcp.keepalive = 60000 # in milliseconds
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HttpConfig {
+    /// accept only HTTP1 connections.
+    pub http1_only: bool,
+    /// accept only HTTP2 connections.
+    pub http2_only: bool,
+    /// timeout for reading HTTP1 request headers.
+    pub http1_header_read_timeout: Option<Duration>,
+    /// interval of HTTP2 keep-alive pings, pings are disabled if not set.
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// timeout for receiving acknowledgement of HTTP2 keep-alive ping.
+    pub http2_keep_alive_timeout: Option<Duration>,
+    /// maximum number of concurrent HTTP2 streams per connection.
+    pub http2_max_concurrent_streams: Option<u32>,
+    /// set `TCP_NODELAY` on accepted connections.
+    pub tcp_nodelay: bool,
+    /// idle time before TCP keepalive probes are sent, keepalive is disabled if not set.
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl<'de> Deserialize<'de> for HttpConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let flag = |ptr| {
+            config
+                .pointer_and_deserialize_optional::<bool, D::Error>(ptr)
+                .map(Option::unwrap_or_default)
+        };
+        let millis = |ptr| {
+            config
+                .pointer_and_deserialize_optional::<Millis, D::Error>(ptr)
+                .map(|millis| millis.map(|Millis(duration)| duration))
+        };
+
+        Ok(Self {
+            http1_only: flag(HTTP1_ONLY_PTR)?,
+            http2_only: flag(HTTP2_ONLY_PTR)?,
+            http1_header_read_timeout: millis(HTTP1_HEADER_READ_TIMEOUT_PTR)?,
+            http2_keep_alive_interval: millis(HTTP2_KEEP_ALIVE_INTERVAL_PTR)?,
+            http2_keep_alive_timeout: millis(HTTP2_KEEP_ALIVE_TIMEOUT_PTR)?,
+            http2_max_concurrent_streams: config
+                .pointer_and_deserialize_optional::<u32, D::Error>(
+                    HTTP2_MAX_CONCURRENT_STREAMS_PTR,
+                )?,
+            tcp_nodelay: flag(TCP_NODELAY_PTR)?,
+            tcp_keepalive: millis(TCP_KEEPALIVE_PTR)?,
+        })
+    }
+}
@@ -0,0 +1,47 @@
+use crate::configuration::units::Count;
+use crate::extensions::DeserializeExt;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+const MAX_CONNECTIONS_PTR: &str = "/max/connections";
+const MAX_IN_FLIGHT_REQUESTS_PTR: &str = "/max/in/flight/requests";
+
+/// Connection and concurrency limits of application listener.
+/// Management endpoints served by a separate listener are not limited.\
+/// Limits are read from number or string like `10k`, invalid values fail config load.
+/// ```toml
+/// [server.limits]
+/// max.connections = "10k"
+/// max.in.flight.requests = 1000
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LimitsConfig {
+    /// Maximum number of open connections.
+    /// Connections accepted above the limit are closed immediately. Unlimited if not set.
+    pub max_connections: Option<usize>,
+    /// Maximum number of requests processed at the same time.
+    /// Requests above the limit are rejected with [`503 Service Unavailable`](axum::http::StatusCode::SERVICE_UNAVAILABLE)
+    /// or gRPC `ResourceExhausted` status. Unlimited if not set.
+    pub max_in_flight_requests: Option<usize>,
+}
+
+impl<'de> Deserialize<'de> for LimitsConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let max_connections = config
+            .pointer_and_deserialize_optional::<Count, D::Error>(MAX_CONNECTIONS_PTR)?
+            .map(|Count(count)| count);
+        let max_in_flight_requests = config
+            .pointer_and_deserialize_optional::<Count, D::Error>(MAX_IN_FLIGHT_REQUESTS_PTR)?
+            .map(|Count(count)| count);
+
+        Ok(Self {
+            max_connections,
+            max_in_flight_requests,
+        })
+    }
+}
@@ -1,26 +1,138 @@
 use crate::extensions::DeserializeExt;
+use crate::observability::{from_str_to_filter, Filter, SANITIZED_VALUE};
 use crate::static_assert;
+use ipnet::IpNet;
 use serde::de::{Error, Unexpected};
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
+use std::fmt::{Debug, Formatter};
+use std::net::IpAddr;
 
 const HEALTH_ENDPOINT: &str = "/health";
 const LIVE_ENDPOINT: &str = "/live";
 const READY_ENDPOINT: &str = "/ready";
+const STARTUP_ENDPOINT: &str = "/startup";
 const METRICS_ENDPOINT: &str = "/metrics";
 const VERSION_ENDPOINT: &str = "/version";
+const CONFIG_ENDPOINT: &str = "/config";
+const DIAGNOSTICS_ENDPOINT: &str = "/diagnostics";
+const PROFILE_ENDPOINT: &str = "/debug/pprof/profile";
+const FLAMEGRAPH_ENDPOINT: &str = "/debug/pprof/flamegraph";
+const LOG_LEVEL_ENDPOINT: &str = "/loglevel";
 
 const HEALTH_PTR: &str = "/health";
 const LIVE_PTR: &str = "/live";
 const READY_PTR: &str = "/ready";
+const STARTUP_PTR: &str = "/startup";
 const METRICS_PTR: &str = "/metrics";
 const VERSION_PTR: &str = "/version";
+const CONFIG_PTR: &str = "/config";
+const DIAGNOSTICS_PTR: &str = "/diagnostics";
+const PROFILE_PTR: &str = "/profile";
+const FLAMEGRAPH_PTR: &str = "/flamegraph";
+const LOG_LEVEL_PTR: &str = "/loglevel";
+
+const ENABLED_PTR: &str = "/enabled";
+const SANITIZE_PTR: &str = "/sanitize";
+const DEFAULT_SANITIZE: &str = "password,secret,token,api_key,private_key";
+
+const TOKEN_PTR: &str = "/token";
+const BASIC_USERNAME_PTR: &str = "/basic/username";
+const BASIC_PASSWORD_PTR: &str = "/basic/password";
+const ALLOWED_IPS_PTR: &str = "/allowed/ips";
+const EXEMPT_PROBES_PTR: &str = "/exempt/probes";
 
 #[derive(Debug, Default, Clone, Deserialize)]
-/// [`Management`](https://github.com/elefant-dev/fregate-rs/blob/main/src/application/management.rs) configuration. Currently only endpoints configuration is supported.
+/// [`Management`](https://github.com/elefant-dev/fregate-rs/blob/main/src/application/management.rs) configuration.
 pub struct ManagementConfig {
     /// health and metrics endpoints.
+    #[serde(default)]
     pub endpoints: Endpoints,
+    /// host address for management listener. If not set [`crate::AppConfig::host`] is used.\
+    /// Has effect only if `port` is set.
+    #[serde(default)]
+    pub host: Option<IpAddr>,
+    /// If set, management endpoints are served on a separate listener bound to this port
+    /// and are not reachable through application port.\
+    /// Management listener always serves plain HTTP, even if application is started with TLS.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// configures endpoint returning effective configuration.
+    #[serde(default, rename = "config")]
+    pub config_dump: ConfigDumpConfig,
+    /// configures changing log levels through management endpoint.
+    #[serde(default)]
+    pub loglevel: LogLevelConfig,
+    /// access control for management endpoints.
+    #[serde(default)]
+    pub auth: ManagementAuthConfig,
+}
+
+/// Configuration of management endpoint returning [`crate::EffectiveConfig`] as json.\
+/// Disabled by default, might be enabled with `<PREFIX>_MANAGEMENT_CONFIG_ENABLED=true`.\
+/// Values under keys containing any of comma separated `<PREFIX>_MANAGEMENT_CONFIG_SANITIZE` entries (case insensitive) are masked,
+/// `*` masks all values. By default: `password,secret,token,api_key,private_key`,
+/// generic `key` is not included as it would mask keys like `server.tls.key.path`.
+#[derive(Debug, Clone)]
+pub struct ConfigDumpConfig {
+    /// if set to true endpoint is added to management router.
+    pub enabled: bool,
+    /// keys to be masked.
+    pub sanitize: Filter,
+}
+
+impl Default for ConfigDumpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sanitize: from_str_to_filter(Some(DEFAULT_SANITIZE.to_owned())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ConfigDumpConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        let enabled = value
+            .pointer_and_deserialize::<_, D::Error>(ENABLED_PTR)
+            .unwrap_or_default();
+        let sanitize = value
+            .pointer_and_deserialize::<String, D::Error>(SANITIZE_PTR)
+            .unwrap_or_else(|_| DEFAULT_SANITIZE.to_owned());
+
+        Ok(Self {
+            enabled,
+            sanitize: from_str_to_filter(Some(sanitize)),
+        })
+    }
+}
+
+/// Configuration of `PUT` method of management endpoint changing log and trace levels at runtime.\
+/// Disabled by default, might be enabled with `<PREFIX>_MANAGEMENT_LOGLEVEL_ENABLED=true`.
+/// Current levels are always available with `GET` method.
+#[derive(Debug, Default, Clone)]
+pub struct LogLevelConfig {
+    /// if set to true levels might be changed with `PUT` request.
+    pub enabled: bool,
+}
+
+impl<'de> Deserialize<'de> for LogLevelConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        let enabled = value
+            .pointer_and_deserialize_optional::<_, D::Error>(ENABLED_PTR)?
+            .unwrap_or_default();
+
+        Ok(Self { enabled })
+    }
 }
 
 /// By default endpoints are:
@@ -28,8 +140,14 @@ pub struct ManagementConfig {
 /// const HEALTH_ENDPOINT: &str = "/health";
 /// const LIVE_ENDPOINT: &str = "/live";
 /// const READY_ENDPOINT: &str = "/ready";
+/// const STARTUP_ENDPOINT: &str = "/startup";
 /// const METRICS_ENDPOINT: &str = "/metrics";
 /// const VERSION_ENDPOINT: &str = "/version";
+/// const CONFIG_ENDPOINT: &str = "/config";
+/// const DIAGNOSTICS_ENDPOINT: &str = "/diagnostics";
+/// const PROFILE_ENDPOINT: &str = "/debug/pprof/profile";
+/// const FLAMEGRAPH_ENDPOINT: &str = "/debug/pprof/flamegraph";
+/// const LOG_LEVEL_ENDPOINT: &str = "/loglevel";
 /// ```
 /// You might want to change those:\
 /// Example:
@@ -69,10 +187,22 @@ pub struct Endpoints {
     pub live: Endpoint,
     /// ready endpoint
     pub ready: Endpoint,
+    /// startup endpoint
+    pub startup: Endpoint,
     /// metrics endpoint
     pub metrics: Endpoint,
     /// version endpoint
     pub version: Endpoint,
+    /// effective configuration endpoint, see [`ConfigDumpConfig`]
+    pub config: Endpoint,
+    /// runtime diagnostics endpoint, available only with `diagnostics` feature
+    pub diagnostics: Endpoint,
+    /// CPU profile endpoint in pprof format, available only with `profiling` feature
+    pub profile: Endpoint,
+    /// CPU profile flamegraph endpoint, available only with `profiling` feature
+    pub flamegraph: Endpoint,
+    /// endpoint to read and change log and trace levels at runtime
+    pub loglevel: Endpoint,
 }
 
 #[allow(clippy::indexing_slicing)]
@@ -84,8 +214,14 @@ impl<'de> Deserialize<'de> for Endpoints {
         static_assert!(HEALTH_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(LIVE_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(READY_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(STARTUP_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(METRICS_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(VERSION_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(CONFIG_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(DIAGNOSTICS_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(PROFILE_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(FLAMEGRAPH_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(LOG_LEVEL_ENDPOINT.as_bytes()[0] == b'/');
 
         let value = Value::deserialize(deserializer)?;
 
@@ -98,19 +234,43 @@ impl<'de> Deserialize<'de> for Endpoints {
         let ready = value
             .pointer_and_deserialize::<_, D::Error>(READY_PTR)
             .unwrap_or_else(|_| Endpoint(READY_ENDPOINT.to_owned()));
+        let startup = value
+            .pointer_and_deserialize::<_, D::Error>(STARTUP_PTR)
+            .unwrap_or_else(|_| Endpoint(STARTUP_ENDPOINT.to_owned()));
         let metrics = value
             .pointer_and_deserialize::<_, D::Error>(METRICS_PTR)
             .unwrap_or_else(|_| Endpoint(METRICS_ENDPOINT.to_owned()));
         let version = value
             .pointer_and_deserialize::<_, D::Error>(VERSION_PTR)
             .unwrap_or_else(|_| Endpoint(VERSION_ENDPOINT.to_owned()));
+        let config = value
+            .pointer_and_deserialize::<_, D::Error>(CONFIG_PTR)
+            .unwrap_or_else(|_| Endpoint(CONFIG_ENDPOINT.to_owned()));
+        let diagnostics = value
+            .pointer_and_deserialize::<_, D::Error>(DIAGNOSTICS_PTR)
+            .unwrap_or_else(|_| Endpoint(DIAGNOSTICS_ENDPOINT.to_owned()));
+        let profile = value
+            .pointer_and_deserialize::<_, D::Error>(PROFILE_PTR)
+            .unwrap_or_else(|_| Endpoint(PROFILE_ENDPOINT.to_owned()));
+        let flamegraph = value
+            .pointer_and_deserialize::<_, D::Error>(FLAMEGRAPH_PTR)
+            .unwrap_or_else(|_| Endpoint(FLAMEGRAPH_ENDPOINT.to_owned()));
+        let loglevel = value
+            .pointer_and_deserialize::<_, D::Error>(LOG_LEVEL_PTR)
+            .unwrap_or_else(|_| Endpoint(LOG_LEVEL_ENDPOINT.to_owned()));
 
         Ok(Endpoints {
             health,
             live,
             ready,
+            startup,
             metrics,
             version,
+            config,
+            diagnostics,
+            profile,
+            flamegraph,
+            loglevel,
         })
     }
 }
@@ -121,19 +281,149 @@ impl Default for Endpoints {
         static_assert!(HEALTH_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(LIVE_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(READY_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(STARTUP_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(METRICS_ENDPOINT.as_bytes()[0] == b'/');
         static_assert!(VERSION_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(CONFIG_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(DIAGNOSTICS_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(PROFILE_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(FLAMEGRAPH_ENDPOINT.as_bytes()[0] == b'/');
+        static_assert!(LOG_LEVEL_ENDPOINT.as_bytes()[0] == b'/');
 
         Self {
             health: Endpoint(HEALTH_ENDPOINT.to_owned()),
             live: Endpoint(LIVE_ENDPOINT.to_owned()),
             ready: Endpoint(READY_ENDPOINT.to_owned()),
+            startup: Endpoint(STARTUP_ENDPOINT.to_owned()),
             metrics: Endpoint(METRICS_ENDPOINT.to_owned()),
             version: Endpoint(VERSION_ENDPOINT.to_owned()),
+            config: Endpoint(CONFIG_ENDPOINT.to_owned()),
+            diagnostics: Endpoint(DIAGNOSTICS_ENDPOINT.to_owned()),
+            profile: Endpoint(PROFILE_ENDPOINT.to_owned()),
+            flamegraph: Endpoint(FLAMEGRAPH_ENDPOINT.to_owned()),
+            loglevel: Endpoint(LOG_LEVEL_ENDPOINT.to_owned()),
         }
     }
 }
 
+/// Access control for management endpoints, disabled if nothing is set.\
+/// If `token` or basic credentials are set, requests must have matching `Authorization: Bearer <token>`
+/// or `Authorization: Basic <credentials>` header, otherwise `401 Unauthorized` is returned.\
+/// If `allowed_ips` is set, requests from other addresses are rejected with `403 Forbidden`.
+/// Unix domain socket connections have no address, so application fails to start if management endpoints are served on Unix domain socket with `allowed_ips` set.\
+/// Configuration fails to load if only one of basic credentials is set or if any of `allowed_ips` entries is invalid.
+/// Numeric values are read as strings, but as environment variables are parsed as numbers leading zeros are lost.\
+/// Example:
+/// ```no_run
+/// std::env::set_var("TEST_MANAGEMENT_AUTH_TOKEN", "secret");
+/// std::env::set_var("TEST_MANAGEMENT_AUTH_BASIC_USERNAME", "admin");
+/// std::env::set_var("TEST_MANAGEMENT_AUTH_BASIC_PASSWORD", "password");
+/// std::env::set_var("TEST_MANAGEMENT_AUTH_ALLOWED_IPS", "127.0.0.1,10.0.0.0/8");
+/// std::env::set_var("TEST_MANAGEMENT_AUTH_EXEMPT_PROBES", "true");
+/// ```
+#[derive(Clone)]
+pub struct ManagementAuthConfig {
+    /// static bearer token.
+    pub token: Option<String>,
+    /// username for basic auth, basic auth is enabled if set.
+    pub basic_username: Option<String>,
+    /// password for basic auth, must be set together with `basic_username`.
+    pub basic_password: Option<String>,
+    /// comma separated list of allowed addresses or networks, e.g. `127.0.0.1,10.0.0.0/8`.
+    pub allowed_ips: Option<Vec<IpNet>>,
+    /// if set to true health, live, ready and startup endpoints are not protected, so probes keep working. By default `true`.
+    pub exempt_probes: bool,
+}
+
+impl ManagementAuthConfig {
+    /// Returns true if any of access control options is set.
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some() || self.basic_username.is_some() || self.allowed_ips.is_some()
+    }
+}
+
+impl Default for ManagementAuthConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            basic_username: None,
+            basic_password: None,
+            allowed_ips: None,
+            exempt_probes: true,
+        }
+    }
+}
+
+impl Debug for ManagementAuthConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sanitize = |value: &Option<String>| value.as_ref().map(|_| SANITIZED_VALUE);
+
+        f.debug_struct("ManagementAuthConfig")
+            .field("token", &sanitize(&self.token))
+            .field("basic_username", &self.basic_username)
+            .field("basic_password", &sanitize(&self.basic_password))
+            .field("allowed_ips", &self.allowed_ips)
+            .field("exempt_probes", &self.exempt_probes)
+            .finish()
+    }
+}
+
+impl<'de> Deserialize<'de> for ManagementAuthConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+
+        let token = scalar_to_string::<D::Error>(&value, TOKEN_PTR)?;
+        let basic_username = scalar_to_string::<D::Error>(&value, BASIC_USERNAME_PTR)?;
+        let basic_password = scalar_to_string::<D::Error>(&value, BASIC_PASSWORD_PTR)?;
+        if basic_username.is_some() != basic_password.is_some() {
+            return Err(D::Error::custom(
+                "basic.username and basic.password must be set together",
+            ));
+        }
+        let allowed_ips = scalar_to_string::<D::Error>(&value, ALLOWED_IPS_PTR)?
+            .map(|ips| {
+                ips.split(',')
+                    .map(str::trim)
+                    .filter(|ip| !ip.is_empty())
+                    .map(|ip| {
+                        ip.parse::<IpNet>()
+                            .or_else(|_| ip.parse::<IpAddr>().map(IpNet::from))
+                            .map_err(|_| {
+                                D::Error::custom(format!(
+                                    "{ALLOWED_IPS_PTR}: invalid address or network `{ip}`"
+                                ))
+                            })
+                    })
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()?;
+        let exempt_probes = value
+            .pointer_and_deserialize::<_, D::Error>(EXEMPT_PROBES_PTR)
+            .unwrap_or(true);
+
+        Ok(Self {
+            token,
+            basic_username,
+            basic_password,
+            allowed_ips,
+            exempt_probes,
+        })
+    }
+}
+
+/// Reads scalar as string, so numeric values like `password = 1234` are not lost.
+fn scalar_to_string<E: Error>(value: &Value, pointer: &'static str) -> Result<Option<String>, E> {
+    match value.pointer(pointer) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(value @ (Value::Number(_) | Value::Bool(_))) => Ok(Some(value.to_string())),
+        Some(_) => Err(E::custom(format!("{pointer}: expected string"))),
+    }
+}
+
 #[derive(Debug, Clone)]
 /// This is simply a wrapper over [`String`] but it checks if [`String`] starts with '/' symbol.
 pub struct Endpoint(String);
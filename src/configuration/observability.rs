@@ -1,3 +1,4 @@
+use crate::configuration::units::{ByteSize, Millis, Secs};
 use crate::extensions::DeserializeExt;
 use crate::observability::HeadersFilter;
 use serde::de::Error;
@@ -37,7 +38,7 @@ pub struct ObservabilityConfig {
     pub logger_config: LoggerConfig,
     /// if it set true then metrics will be supplied from cgroup v2.
     pub cgroup_metrics: bool,
-    /// metrics update interval
+    /// metrics update interval, read from number of milliseconds or string like `1s`
     pub metrics_update_interval: Duration,
     /// trace level read to string and later parsed into EnvFilter
     pub trace_level: String,
@@ -55,15 +56,16 @@ pub struct LoggerConfig {
     /// log file prefix, if written to file all log files will be with this prefix.\
     /// by default `component_name`  from [`ObservabilityConfig`] is used
     pub logging_file: Option<String>,
-    /// Maximum message field length, if set: message field will be cut if len() exceed this limit
+    /// Maximum message field length, if set: message field will be cut if len() exceed this limit.\
+    /// read from number of bytes or string like `8KiB`
     pub msg_length: Option<usize>,
     /// Sets limit for [`tracing_appender::non_blocking::NonBlocking`]
     pub buffered_lines_limit: Option<usize>,
-    /// interval to split file into chunks with fixed interval
+    /// interval to split file into chunks with fixed interval, read from number of seconds or string like `1h`
     pub logging_interval: Option<Duration>,
-    /// file size limit in bytes
+    /// file size limit in bytes, read from number of bytes or string like `100MiB`
     pub logging_max_file_size: Option<usize>,
-    /// maximum duration files kept in seconds
+    /// maximum duration files kept, read from number of seconds or string like `7d`
     pub logging_max_history: Option<Duration>,
     /// maximum number of files kept
     pub logging_max_file_count: Option<usize>,
@@ -94,8 +96,8 @@ impl<'de> Deserialize<'de> for ObservabilityConfig {
             .map(from_value::<String>)
             .transpose()
             .map_err(D::Error::custom)?;
-        let metrics_update_interval =
-            config.pointer_and_deserialize::<u64, D::Error>(SERVER_METRICS_UPDATE_INTERVAL_PTR)?;
+        let Millis(metrics_update_interval) =
+            config.pointer_and_deserialize::<_, D::Error>(SERVER_METRICS_UPDATE_INTERVAL_PTR)?;
         let logger_config = LoggerConfig::deserialize(config).map_err(Error::custom)?;
 
         Ok(ObservabilityConfig {
@@ -104,7 +106,7 @@ impl<'de> Deserialize<'de> for ObservabilityConfig {
             service_name,
             component_name,
             traces_endpoint,
-            metrics_update_interval: Duration::from_millis(metrics_update_interval),
+            metrics_update_interval,
             cgroup_metrics,
             logger_config,
         })
@@ -120,8 +122,9 @@ impl<'de> Deserialize<'de> for LoggerConfig {
 
         let log_level = config.pointer_and_deserialize(LOG_LEVEL_PTR)?;
         let msg_length = config
-            .pointer_and_deserialize::<_, D::Error>(LOG_MSG_LENGTH_PTR)
-            .ok();
+            .pointer_and_deserialize::<ByteSize, D::Error>(LOG_MSG_LENGTH_PTR)
+            .ok()
+            .map(|ByteSize(size)| size);
         let buffered_lines_limit = config
             .pointer_and_deserialize::<_, D::Error>(BUFFERED_LINES_LIMIT_PTR)
             .ok();
@@ -136,16 +139,17 @@ impl<'de> Deserialize<'de> for LoggerConfig {
             .ok();
 
         let logging_interval = config
-            .pointer_and_deserialize::<u64, D::Error>(LOGGING_INTERVAL_PTR)
+            .pointer_and_deserialize::<Secs, D::Error>(LOGGING_INTERVAL_PTR)
             .ok()
-            .map(Duration::from_secs);
+            .map(|Secs(interval)| interval);
         let logging_max_file_size = config
-            .pointer_and_deserialize::<_, D::Error>(LOGGING_MAX_FILE_SIZE_PTR)
-            .ok();
+            .pointer_and_deserialize::<ByteSize, D::Error>(LOGGING_MAX_FILE_SIZE_PTR)
+            .ok()
+            .map(|ByteSize(size)| size);
         let logging_max_history = config
-            .pointer_and_deserialize::<u64, D::Error>(LOGGING_MAX_HISTORY_PTR)
+            .pointer_and_deserialize::<Secs, D::Error>(LOGGING_MAX_HISTORY_PTR)
             .ok()
-            .map(Duration::from_secs);
+            .map(|Secs(history)| history);
         let logging_max_file_count = config
             .pointer_and_deserialize::<_, D::Error>(LOGGING_MAX_FILE_COUNT_PTR)
             .ok();
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+/// Env variable with name of active profile, see [`crate::AppConfigBuilder::add_active_profile`].
+pub const PROFILE_ENV: &str = "FREGATE_PROFILE";
+/// Env variable with directory profile files are read from, by default current directory.
+pub const CONFIG_DIR_ENV: &str = "FREGATE_CONFIG_DIR";
+
+const DEFAULT_CONFIG_DIR: &str = ".";
+const BASE_FILE: &str = "app";
+const LOCAL_PROFILE: &str = "local";
+const FILE_EXTENSION: &str = "yaml";
+
+/// Returns config directory and name of active profile if [`PROFILE_ENV`] is set.
+pub(crate) fn active_profile() -> Option<(String, String)> {
+    let profile = std::env::var(PROFILE_ENV)
+        .ok()
+        .filter(|profile| !profile.is_empty())?;
+    let dir = std::env::var(CONFIG_DIR_ENV).unwrap_or_else(|_| DEFAULT_CONFIG_DIR.to_owned());
+
+    Some((dir, profile))
+}
+
+/// Returns profile files in order of precedence, each with flag if file is required.
+pub(crate) fn profile_files(dir: &str, profile: &str) -> Vec<(String, bool)> {
+    let path = |name: &str| {
+        Path::new(dir)
+            .join(format!("{name}.{FILE_EXTENSION}"))
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    let mut files = vec![
+        (path(BASE_FILE), true),
+        (path(&format!("{BASE_FILE}-{profile}")), true),
+    ];
+    if profile != LOCAL_PROFILE {
+        files.push((path(&format!("{BASE_FILE}-{LOCAL_PROFILE}")), false));
+    }
+    files
+}
+
+/// Returns existing files of active profile.
+pub(crate) fn active_profile_files() -> Vec<String> {
+    active_profile()
+        .map(|(dir, profile)| profile_files(&dir, &profile))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(path, _)| Path::new(path).is_file())
+        .map(|(path, _)| path)
+        .collect()
+}
+
+#[cfg(test)]
+mod profile_test {
+    use super::*;
+
+    #[test]
+    fn profile_files_order() {
+        let files = profile_files("config", "dev");
+        assert_eq!(
+            files,
+            [
+                ("config/app.yaml".to_owned(), true),
+                ("config/app-dev.yaml".to_owned(), true),
+                ("config/app-local.yaml".to_owned(), false),
+            ]
+        );
+        assert_eq!(profile_files("config", "local").len(), 2);
+    }
+}
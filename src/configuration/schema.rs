@@ -0,0 +1,391 @@
+use crate::configuration::args::parse_value;
+use crate::{AppConfig, Empty};
+use serde_json::{json, Map, Value};
+
+const JSON_SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+const INDENT: &str = "  ";
+
+/// Type of configuration value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// `true` or `false`
+    Bool,
+    /// integer in range `0..=65535`
+    U16,
+    /// non-negative 32-bit integer
+    U32,
+    /// non-negative integer
+    U64,
+    /// non-negative integer
+    Usize,
+    /// any string
+    String,
+    /// IPv4 or IPv6 address
+    IpAddr,
+    /// comma separated IP addresses or networks, e.g. `127.0.0.1,10.0.0.0/8`
+    IpList,
+    /// [`tracing_subscriber::EnvFilter`] directives, e.g. `info,hyper=warn`
+    Level,
+    /// path starting with `/`
+    Endpoint,
+    /// non-negative integer in unit of the key or string like `500ms`, `30s` or `1h30m`
+    Duration,
+    /// non-negative integer of bytes or string like `512KB` or `100MiB`
+    ByteSize,
+    /// non-negative integer or string like `10k` or `1M`
+    Count,
+}
+
+impl ValueKind {
+    fn is_string(self) -> bool {
+        matches!(
+            self,
+            ValueKind::String
+                | ValueKind::IpAddr
+                | ValueKind::IpList
+                | ValueKind::Level
+                | ValueKind::Endpoint
+        )
+    }
+
+    fn json_schema(self) -> Value {
+        match self {
+            ValueKind::Bool => json!({"type": "boolean"}),
+            ValueKind::U16 => json!({"type": "integer", "minimum": 0, "maximum": u16::MAX}),
+            ValueKind::U32 => json!({"type": "integer", "minimum": 0, "maximum": u32::MAX}),
+            ValueKind::U64 | ValueKind::Usize => json!({"type": "integer", "minimum": 0}),
+            ValueKind::String | ValueKind::IpList | ValueKind::Level => json!({"type": "string"}),
+            ValueKind::IpAddr => {
+                json!({"type": "string", "anyOf": [{"format": "ipv4"}, {"format": "ipv6"}]})
+            }
+            ValueKind::Endpoint => json!({"type": "string", "pattern": "^/"}),
+            ValueKind::Duration | ValueKind::ByteSize | ValueKind::Count => {
+                json!({"anyOf": [{"type": "integer", "minimum": 0}, {"type": "string"}]})
+            }
+        }
+    }
+}
+
+/// Documented configuration key, see [`ConfigSchema`].
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigKey {
+    /// dot separated path from the root of configuration, e.g. `server.http.tcp.keepalive`.
+    pub key: &'static str,
+    /// type of value.
+    pub kind: ValueKind,
+    /// if true value must be present in configuration.
+    pub required: bool,
+    /// default value as written in config file.
+    pub default: Option<&'static str>,
+    /// description used in JSON Schema and in example config.
+    pub description: &'static str,
+}
+
+impl ConfigKey {
+    const fn required(
+        key: &'static str,
+        kind: ValueKind,
+        default: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            key,
+            kind,
+            required: true,
+            default: Some(default),
+            description,
+        }
+    }
+
+    const fn optional(
+        key: &'static str,
+        kind: ValueKind,
+        default: Option<&'static str>,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            key,
+            kind,
+            required: false,
+            default,
+            description,
+        }
+    }
+}
+
+/// Describes keys of `private` section of [`AppConfig`] for [`AppConfig::json_schema`] and [`AppConfig::example_config`].\
+/// As `private` is deserialized from the root of configuration, keys are dot separated paths from the root.
+///
+/// Example:
+/// ```rust
+/// use fregate::{AppConfig, ConfigKey, ConfigSchema, ValueKind};
+///
+/// struct Private;
+///
+/// impl ConfigSchema for Private {
+///     fn config_keys() -> Vec<ConfigKey> {
+///         vec![ConfigKey {
+///             key: "db.url",
+///             kind: ValueKind::String,
+///             required: true,
+///             default: None,
+///             description: "Database connection url.",
+///         }]
+///     }
+/// }
+///
+/// let schema = AppConfig::<Private>::json_schema();
+/// assert_eq!(schema.pointer("/properties/db/required/0"), Some(&"url".into()));
+/// ```
+pub trait ConfigSchema {
+    /// Returns documented keys of `private` section.
+    fn config_keys() -> Vec<ConfigKey>;
+}
+
+impl ConfigSchema for Empty {
+    fn config_keys() -> Vec<ConfigKey> {
+        Vec::new()
+    }
+}
+
+/// Keys of built-in sections of [`AppConfig`].
+pub(crate) const KEYS: &[ConfigKey] = {
+    use ValueKind::*;
+
+    &[
+        ConfigKey::required("host", IpAddr, "0.0.0.0", "Address application listens on."),
+        ConfigKey::required("port", U16, "8000", "Port application listens on."),
+        ConfigKey::optional("server.port", U16, None, "Port application listens on, has priority over `port`."),
+        ConfigKey::required("service.name", String, "default", "Service name used in logs, traces and metrics."),
+        ConfigKey::required("component.name", String, "default", "Component name used in logs, traces and metrics."),
+        ConfigKey::required("component.version", String, "default", "Component version used in logs and traces."),
+        ConfigKey::required("log.level", Level, "info", "Log filter directives."),
+        ConfigKey::optional("log.msg.length", ByteSize, Some("8192"), "Max length of log message, number of bytes or size like `8KiB`."),
+        ConfigKey::required("trace.level", Level, "info", "Trace filter directives."),
+        ConfigKey::optional("exporter.otlp.traces.endpoint", String, None, "OTLP collector endpoint, traces are not exported if not set."),
+        ConfigKey::optional("buffered.lines.limit", Usize, None, "Max number of log lines buffered by non-blocking writer."),
+        ConfigKey::optional("logging.path", String, None, "Directory to write log files to."),
+        ConfigKey::optional("logging.file", String, None, "Log file name prefix, `component.name` by default."),
+        ConfigKey::optional("logging.interval", Duration, None, "Log file rotation interval, number of seconds or duration like `1h`."),
+        ConfigKey::optional("logging.max.file.size", ByteSize, None, "Max log file size before rotation, number of bytes or size like `100MiB`."),
+        ConfigKey::optional("logging.max.history", Duration, None, "Max age of rotated log files, number of seconds or duration like `7d`."),
+        ConfigKey::optional("logging.max.file.count", Usize, None, "Max number of rotated log files."),
+        ConfigKey::optional("logging.enable.compression", Bool, Some("false"), "Compress rotated log files."),
+        ConfigKey::optional("cgroup.metrics", Bool, Some("false"), "Collect cgroup metrics instead of system metrics."),
+        ConfigKey::required("server.metrics.update_interval", Duration, "1000", "Interval of system metrics update, number of milliseconds or duration like `1s`."),
+        ConfigKey::required("server.tls.handshake_timeout", Duration, "10000", "TLS handshake timeout, number of milliseconds or duration like `10s`."),
+        ConfigKey::optional("server.tls.key.path", String, None, "Path to TLS private key."),
+        ConfigKey::optional("server.tls.cert.path", String, None, "Path to TLS certificate."),
+        ConfigKey::optional("server.http.http1.only", Bool, Some("false"), "Serve only HTTP/1."),
+        ConfigKey::optional("server.http.http2.only", Bool, Some("false"), "Serve only HTTP/2."),
+        ConfigKey::optional("server.http.http1.header.read.timeout", Duration, None, "HTTP/1 header read timeout, number of milliseconds or duration like `30s`."),
+        ConfigKey::optional("server.http.http2.keep.alive.interval", Duration, None, "HTTP/2 keep-alive ping interval, number of milliseconds or duration like `30s`."),
+        ConfigKey::optional("server.http.http2.keep.alive.timeout", Duration, None, "HTTP/2 keep-alive ping timeout, number of milliseconds or duration like `30s`."),
+        ConfigKey::optional("server.http.http2.max.concurrent.streams", U32, None, "Max number of concurrent HTTP/2 streams per connection."),
+        ConfigKey::optional("server.http.tcp.nodelay", Bool, Some("false"), "Set TCP_NODELAY on accepted connections."),
+        ConfigKey::optional("server.http.tcp.keepalive", Duration, None, "TCP keepalive interval, number of milliseconds or duration like `30s`."),
+        ConfigKey::optional("server.limits.max.connections", Count, None, "Max number of concurrent connections, number or count like `10k`."),
+        ConfigKey::optional("server.limits.max.in.flight.requests", Count, None, "Max number of requests processed concurrently, number or count like `1k`."),
+        ConfigKey::optional("shutdown.pre.stop.delay", Duration, Some("0"), "Delay before graceful shutdown starts, number of milliseconds or duration like `5s`."),
+        ConfigKey::optional("shutdown.drain.timeout", Duration, None, "Max time to wait for in-flight requests on shutdown, number of milliseconds or duration like `30s`."),
+        ConfigKey::optional("headers.include", String, Some("*"), "Comma separated headers to log, `*` for all."),
+        ConfigKey::optional("headers.exclude", String, None, "Comma separated headers not to log, `*` for all."),
+        ConfigKey::optional("headers.sanitize", String, None, "Comma separated headers to log masked, `*` for all."),
+        ConfigKey::optional("management.host", IpAddr, None, "Address of separate management listener, `host` by default."),
+        ConfigKey::optional("management.port", U16, None, "Port of separate management listener, management endpoints are served on application port if not set."),
+        ConfigKey::optional("management.endpoints.health", Endpoint, Some("/health"), "Health probe endpoint."),
+        ConfigKey::optional("management.endpoints.live", Endpoint, Some("/live"), "Liveness probe endpoint."),
+        ConfigKey::optional("management.endpoints.ready", Endpoint, Some("/ready"), "Readiness probe endpoint."),
+        ConfigKey::optional("management.endpoints.startup", Endpoint, Some("/startup"), "Startup probe endpoint."),
+        ConfigKey::optional("management.endpoints.metrics", Endpoint, Some("/metrics"), "Prometheus metrics endpoint."),
+        ConfigKey::optional("management.endpoints.version", Endpoint, Some("/version"), "Version endpoint."),
+        ConfigKey::optional("management.endpoints.config", Endpoint, Some("/config"), "Effective configuration endpoint."),
+        ConfigKey::optional("management.endpoints.loglevel", Endpoint, Some("/loglevel"), "Runtime log level endpoint."),
+        ConfigKey::optional("management.endpoints.diagnostics", Endpoint, Some("/diagnostics"), "Runtime diagnostics endpoint, requires `diagnostics` feature."),
+        ConfigKey::optional("management.endpoints.profile", Endpoint, Some("/debug/pprof/profile"), "CPU profile endpoint, requires `profiling` feature."),
+        ConfigKey::optional("management.endpoints.flamegraph", Endpoint, Some("/debug/pprof/flamegraph"), "Flamegraph endpoint, requires `profiling` feature."),
+        ConfigKey::optional("management.loglevel.enabled", Bool, Some("false"), "Allow changing log levels with `PUT` request to log level endpoint."),
+        ConfigKey::optional("management.config.enabled", Bool, Some("false"), "Serve effective configuration endpoint."),
+        ConfigKey::optional("management.config.sanitize", String, Some("password,secret,token,api_key,private_key"), "Comma separated key parts masked in effective configuration, `*` for all."),
+        ConfigKey::optional("management.auth.token", String, None, "Bearer token required by management endpoints."),
+        ConfigKey::optional("management.auth.basic.username", String, None, "Basic auth username required by management endpoints."),
+        ConfigKey::optional("management.auth.basic.password", String, None, "Basic auth password required by management endpoints."),
+        ConfigKey::optional("management.auth.allowed.ips", IpList, None, "Comma separated IP addresses and networks allowed to call management endpoints."),
+        ConfigKey::optional("management.auth.exempt.probes", Bool, Some("true"), "Serve probes without authentication."),
+    ]
+};
+
+impl<ConfigExt: ConfigSchema> AppConfig<ConfigExt> {
+    /// Returns JSON Schema of configuration including `private` section described with [`ConfigSchema`].
+    /// Might be used to validate config files in editors.
+    pub fn json_schema() -> Value {
+        let mut schema = Node::tree(ConfigExt::config_keys()).json_schema();
+        if let Value::Object(map) = &mut schema {
+            map.insert("$schema".to_owned(), Value::from(JSON_SCHEMA_DRAFT));
+            map.insert("title".to_owned(), Value::from("AppConfig"));
+        }
+        schema
+    }
+
+    /// Returns YAML config with description of each key, keys without default value are commented out.
+    pub fn example_config() -> String {
+        let mut yaml = String::new();
+        for child in &Node::tree(ConfigExt::config_keys()).children {
+            child.example(0, false, &mut yaml);
+        }
+        yaml
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    name: &'static str,
+    key: Option<ConfigKey>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn tree(private: Vec<ConfigKey>) -> Self {
+        let mut root = Node::default();
+        for key in KEYS.iter().copied().chain(private) {
+            root.insert(key);
+        }
+        root
+    }
+
+    fn insert(&mut self, key: ConfigKey) {
+        let mut node = self;
+        for segment in key.key.split('.') {
+            let idx = match node.children.iter().position(|child| child.name == segment) {
+                Some(idx) => idx,
+                None => {
+                    node.children.push(Node {
+                        name: segment,
+                        ..Default::default()
+                    });
+                    node.children.len() - 1
+                }
+            };
+            let Some(child) = node.children.get_mut(idx) else {
+                return;
+            };
+            node = child;
+        }
+        node.key = Some(key);
+    }
+
+    fn is_required(&self) -> bool {
+        self.key.is_some_and(|key| key.required) || self.children.iter().any(Node::is_required)
+    }
+
+    fn has_default(&self) -> bool {
+        self.key.is_some_and(|key| key.default.is_some())
+            || self.children.iter().any(Node::has_default)
+    }
+
+    fn json_schema(&self) -> Value {
+        if let Some(key) = self.key.filter(|_| self.children.is_empty()) {
+            let mut schema = key.kind.json_schema();
+            if let Value::Object(map) = &mut schema {
+                map.insert("description".to_owned(), Value::from(key.description));
+                if let Some(default) = key.default {
+                    let default = if key.kind.is_string() {
+                        Value::from(default)
+                    } else {
+                        parse_value(default)
+                    };
+                    map.insert("default".to_owned(), default);
+                }
+            }
+            return schema;
+        }
+
+        let properties = self
+            .children
+            .iter()
+            .map(|child| (child.name.to_owned(), child.json_schema()))
+            .collect::<Map<_, _>>();
+        let required = self
+            .children
+            .iter()
+            .filter(|child| child.is_required())
+            .map(|child| child.name)
+            .collect::<Vec<_>>();
+
+        let mut schema = json!({"type": "object", "properties": properties});
+        if let (false, Value::Object(map)) = (required.is_empty(), &mut schema) {
+            map.insert("required".to_owned(), json!(required));
+        }
+        schema
+    }
+
+    fn example(&self, depth: usize, commented: bool, yaml: &mut String) {
+        let indent = INDENT.repeat(depth);
+        let commented = commented || !self.has_default();
+        let comment = if commented { "# " } else { "" };
+
+        match self.key.filter(|_| self.children.is_empty()) {
+            Some(key) => {
+                let required = if key.required { " Required." } else { "" };
+                yaml.push_str(&format!("{indent}# {}{required}\n", key.description));
+                match key.default {
+                    Some(default) if key.kind.is_string() => {
+                        let default = Value::from(default);
+                        yaml.push_str(&format!("{indent}{comment}{}: {default}\n", self.name));
+                    }
+                    Some(default) => {
+                        yaml.push_str(&format!("{indent}{comment}{}: {default}\n", self.name));
+                    }
+                    None => yaml.push_str(&format!("{indent}{comment}{}:\n", self.name)),
+                }
+            }
+            None => {
+                yaml.push_str(&format!("{indent}{comment}{}:\n", self.name));
+                for child in &self.children {
+                    child.example(depth + 1, commented, yaml);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod schema_test {
+    use super::*;
+
+    #[test]
+    fn nests_keys_and_marks_required() {
+        let schema = AppConfig::<Empty>::json_schema();
+
+        assert_eq!(
+            schema.pointer(
+                "/properties/server/properties/http/properties/tcp/properties/keepalive/anyOf/0/type"
+            ),
+            Some(&json!("integer"))
+        );
+        assert_eq!(
+            schema.pointer("/properties/log/properties/msg/properties/length/default"),
+            Some(&json!(8192))
+        );
+        let required = schema.pointer("/required").unwrap().as_array().unwrap();
+        assert!(required.contains(&json!("service")));
+        assert!(!required.contains(&json!("management")));
+    }
+
+    #[test]
+    fn example_config_is_valid_default() {
+        let yaml = AppConfig::<Empty>::example_config();
+        assert!(
+            yaml.contains("  # Log file name prefix, `component.name` by default.\n  # file:\n")
+        );
+
+        let config = AppConfig::<Empty>::builder()
+            .add_str(&yaml, config::FileFormat::Yaml)
+            .strict(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.port, 8000);
+        assert_eq!(config.management_cfg.endpoints.health.as_ref(), "/health");
+    }
+}
@@ -0,0 +1,48 @@
+use crate::configuration::units::Millis;
+use crate::extensions::DeserializeExt;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::time::Duration;
+
+const PRE_STOP_DELAY_PTR: &str = "/pre/stop/delay";
+const DRAIN_TIMEOUT_PTR: &str = "/drain/timeout";
+
+/// Graceful shutdown configuration.
+/// Durations are read from number of milliseconds or string like `5s`, invalid values fail config load.
+/// ```toml
+/// [shutdown]
+/// pre.stop.delay = 5000 # in milliseconds
+/// drain.timeout = "30s"
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ShutdownConfig {
+    /// Delay between termination signal and stop of accepting new connections.\
+    /// During this delay ready endpoint responds with [`503 Service Unavailable`](axum::http::StatusCode::SERVICE_UNAVAILABLE)
+    /// so load balancers have time to remove instance from endpoints.
+    pub pre_stop_delay: Duration,
+    /// Maximum time to wait for in-flight requests to finish.
+    /// When elapsed, remaining connections are forcibly closed. If not set waits indefinitely.
+    pub drain_timeout: Option<Duration>,
+}
+
+impl<'de> Deserialize<'de> for ShutdownConfig {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let config = Value::deserialize(deserializer)?;
+
+        let pre_stop_delay = config
+            .pointer_and_deserialize_optional::<Millis, D::Error>(PRE_STOP_DELAY_PTR)?
+            .map(|Millis(delay)| delay)
+            .unwrap_or_default();
+        let drain_timeout = config
+            .pointer_and_deserialize_optional::<Millis, D::Error>(DRAIN_TIMEOUT_PTR)?
+            .map(|Millis(timeout)| timeout);
+
+        Ok(Self {
+            pre_stop_delay,
+            drain_timeout,
+        })
+    }
+}
@@ -2,6 +2,7 @@ use config::FileFormat;
 
 /// Enum to specify configuration source type:
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum ConfigSource<'a> {
     /// Load from string
     String(&'a str, FileFormat),
@@ -9,4 +10,7 @@ pub enum ConfigSource<'a> {
     File(&'a str),
     /// Read environment variables with specified prefix
     EnvPrefix(&'a str),
+    /// Read `--config <file>` and `--key.path=value` or `--key.path value` command-line arguments, see [`crate::AppConfigBuilder::add_args`].\
+    /// Always has highest precedence regardless of position among other sources.
+    Args,
 }
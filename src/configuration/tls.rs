@@ -1,3 +1,4 @@
+use crate::configuration::units::Millis;
 use crate::extensions::DeserializeExt;
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
@@ -9,7 +10,7 @@ const TLS_CERTIFICATE_PATH: &str = "/server/tls/cert/path";
 
 #[derive(Debug, Default, Clone)]
 pub struct TlsConfigurationVariables {
-    /// TLS handshake timeout
+    /// TLS handshake timeout, read from number of milliseconds or string like `10s`
     pub handshake_timeout: Duration,
     /// path to TLS key file
     pub key_path: Option<Box<str>>,
@@ -24,8 +25,8 @@ impl<'de> Deserialize<'de> for TlsConfigurationVariables {
     {
         let config = Value::deserialize(deserializer)?;
 
-        let tls_handshake_timeout =
-            config.pointer_and_deserialize::<u64, D::Error>(TLS_HANDSHAKE_TIMEOUT)?;
+        let Millis(tls_handshake_timeout) =
+            config.pointer_and_deserialize::<_, D::Error>(TLS_HANDSHAKE_TIMEOUT)?;
         let tls_key_path = config
             .pointer_and_deserialize::<_, D::Error>(TLS_KEY_PATH)
             .ok();
@@ -34,7 +35,7 @@ impl<'de> Deserialize<'de> for TlsConfigurationVariables {
             .ok();
 
         Ok(Self {
-            handshake_timeout: Duration::from_millis(tls_handshake_timeout),
+            handshake_timeout: tls_handshake_timeout,
             key_path: tls_key_path,
             cert_path: tls_cert_path,
         })
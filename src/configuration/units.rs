@@ -0,0 +1,264 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::time::Duration;
+
+const NANOS_PER_MICRO: u64 = 1_000;
+const NANOS_PER_MILLI: u64 = 1_000_000;
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Duration given either as plain number of milliseconds or as string like `500ms`, `30s` or `1h30m`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Millis(pub(crate) Duration);
+
+/// Duration given either as plain number of seconds or as string like `500ms`, `30s` or `1h30m`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Secs(pub(crate) Duration);
+
+/// Size given either as plain number of bytes or as string like `512KB`, `100MiB` or `1.5GiB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ByteSize(pub(crate) usize);
+
+/// Count given either as plain number or as string like `10000`, `10k` or `1M`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Count(pub(crate) usize);
+
+impl<'de> Deserialize<'de> for Millis {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_duration(deserializer, Duration::from_millis).map(Self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Secs {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_duration(deserializer, Duration::from_secs).map(Self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let size = match &value {
+            Value::Number(number) => number.as_u64().ok_or_else(|| {
+                D::Error::custom(format!("expected non-negative integer, got `{number}`"))
+            })?,
+            Value::String(size) => parse_byte_size(size).map_err(D::Error::custom)?,
+            other => {
+                return Err(D::Error::custom(format!(
+                    "expected byte size, got `{other}`"
+                )))
+            }
+        };
+
+        usize::try_from(size)
+            .map(Self)
+            .map_err(|_| D::Error::custom(format!("byte size `{value}` is too large")))
+    }
+}
+
+impl<'de> Deserialize<'de> for Count {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let count = match &value {
+            Value::Number(number) => number.as_u64().ok_or_else(|| {
+                D::Error::custom(format!("expected non-negative integer, got `{number}`"))
+            })?,
+            Value::String(count) => parse_count(count).map_err(D::Error::custom)?,
+            other => return Err(D::Error::custom(format!("expected count, got `{other}`"))),
+        };
+
+        usize::try_from(count)
+            .map(Self)
+            .map_err(|_| D::Error::custom(format!("count `{value}` is too large")))
+    }
+}
+
+fn deserialize_duration<'de, D>(
+    deserializer: D,
+    plain: fn(u64) -> Duration,
+) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(deserializer)? {
+        Value::Number(number) => number.as_u64().map(plain).ok_or_else(|| {
+            D::Error::custom(format!("expected non-negative integer, got `{number}`"))
+        }),
+        Value::String(duration) => match duration.trim().parse::<u64>() {
+            Ok(number) => Ok(plain(number)),
+            Err(_) => parse_duration(&duration).map_err(D::Error::custom),
+        },
+        other => Err(D::Error::custom(format!(
+            "expected duration, got `{other}`"
+        ))),
+    }
+}
+
+/// Parses durations like `500ms`, `30s`, `1.5h` or `1h 30m`.\
+/// Supported units: `ns`, `us`, `ms`, `s`, `m`, `h`, `d`.
+pub(crate) fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let mut rest = duration.trim();
+    if rest.is_empty() {
+        return Err("empty duration".to_owned());
+    }
+
+    let mut total = Duration::ZERO;
+    while !rest.is_empty() {
+        let (number, unit, tail) = split_term(rest);
+        let nanos = match unit {
+            "ns" => 1,
+            "us" | "µs" => NANOS_PER_MICRO,
+            "ms" => NANOS_PER_MILLI,
+            "s" => NANOS_PER_SEC,
+            "m" => 60 * NANOS_PER_SEC,
+            "h" => 60 * 60 * NANOS_PER_SEC,
+            "d" => 24 * 60 * 60 * NANOS_PER_SEC,
+            "" => return Err(format!("missing unit in duration `{duration}`")),
+            unit => return Err(format!("unknown unit `{unit}` in duration `{duration}`")),
+        };
+        let nanos =
+            multiply(number, nanos).ok_or_else(|| format!("invalid duration `{duration}`"))?;
+
+        total = total
+            .checked_add(Duration::from_nanos(nanos))
+            .ok_or_else(|| format!("duration `{duration}` is too large"))?;
+        rest = tail;
+    }
+
+    Ok(total)
+}
+
+/// Parses sizes like `100`, `512KB`, `100MiB` or `1.5 GiB`, units are case insensitive.\
+/// Supported units: `B`, `KB`, `MB`, `GB`, `TB` (powers of 1000) and `KiB`, `MiB`, `GiB`, `TiB` (powers of 1024).
+pub(crate) fn parse_byte_size(size: &str) -> Result<u64, String> {
+    let (number, unit, tail) = split_term(size.trim());
+    if !tail.is_empty() {
+        return Err(format!("invalid byte size `{size}`"));
+    }
+
+    let multiplier: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        unit => return Err(format!("unknown unit `{unit}` in byte size `{size}`")),
+    };
+
+    multiply(number, multiplier).ok_or_else(|| format!("invalid byte size `{size}`"))
+}
+
+/// Parses counts like `10000`, `10k` or `1.5M`, suffixes are case insensitive.\
+/// Supported suffixes: `k` (thousand), `M` (million), `G` (billion).
+pub(crate) fn parse_count(count: &str) -> Result<u64, String> {
+    let (number, unit, tail) = split_term(count.trim());
+    if !tail.is_empty() {
+        return Err(format!("invalid count `{count}`"));
+    }
+
+    let multiplier: u64 = match unit.to_ascii_lowercase().as_str() {
+        "" => 1,
+        "k" => 1_000,
+        "m" => 1_000_000,
+        "g" => 1_000_000_000,
+        unit => return Err(format!("unknown suffix `{unit}` in count `{count}`")),
+    };
+
+    multiply(number, multiplier).ok_or_else(|| format!("invalid count `{count}`"))
+}
+
+/// Splits leading `<number><unit>` term, whitespace between terms and between number and unit is allowed.
+fn split_term(term: &str) -> (&str, &str, &str) {
+    let number_end = term
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(term.len());
+    let (number, tail) = term.split_at(number_end);
+
+    let tail = tail.trim_start();
+    let unit_end = tail
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(tail.len());
+    let (unit, tail) = tail.split_at(unit_end);
+
+    (number, unit, tail.trim_start())
+}
+
+/// Multiplies integer or decimal `number` by `multiplier`, returns None on invalid number or overflow.
+fn multiply(number: &str, multiplier: u64) -> Option<u64> {
+    if let Ok(number) = number.parse::<u64>() {
+        return number.checked_mul(multiplier);
+    }
+
+    let product = number.parse::<f64>().ok()? * multiplier as f64;
+    (product.is_finite() && product >= 0.0 && product < u64::MAX as f64)
+        .then(|| product.round() as u64)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod units_test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("1h 30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("100ns"), Ok(Duration::from_nanos(100)));
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("30 years").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn parses_byte_sizes() {
+        assert_eq!(parse_byte_size("100"), Ok(100));
+        assert_eq!(parse_byte_size("100MiB"), Ok(100 * 1024 * 1024));
+        assert_eq!(parse_byte_size("512 kb"), Ok(512_000));
+        assert_eq!(parse_byte_size("1.5GiB"), Ok(1_610_612_736));
+        assert!(parse_byte_size("1MiB 1KiB").is_err());
+        assert!(parse_byte_size("1XB").is_err());
+    }
+
+    #[test]
+    fn parses_counts() {
+        assert_eq!(parse_count("10000"), Ok(10_000));
+        assert_eq!(parse_count("10k"), Ok(10_000));
+        assert_eq!(parse_count("1.5M"), Ok(1_500_000));
+        assert!(parse_count("10 apples").is_err());
+        assert!(parse_count("").is_err());
+    }
+
+    #[test]
+    fn plain_numbers_keep_unit() {
+        let millis = serde_json::from_value::<Millis>(json!(1000)).unwrap();
+        assert_eq!(millis.0, Duration::from_secs(1));
+
+        let secs = serde_json::from_value::<Secs>(json!("60")).unwrap();
+        assert_eq!(secs.0, Duration::from_secs(60));
+
+        let secs = serde_json::from_value::<Secs>(json!("1h")).unwrap();
+        assert_eq!(secs.0, Duration::from_secs(3600));
+
+        assert!(serde_json::from_value::<Millis>(json!(-1)).is_err());
+        assert!(serde_json::from_value::<ByteSize>(json!(true)).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::configuration::schema::KEYS;
+use crate::configuration::units::{ByteSize, Count, Millis};
+use crate::configuration::{EffectiveConfig, ValueKind};
+use crate::Empty;
+use ipnet::IpNet;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::str::FromStr;
+use tracing_subscriber::EnvFilter;
+
+/// Validation hook for `private` section of [`crate::AppConfig`], called by [`crate::AppConfigBuilder::build`]
+/// if registered with [`crate::AppConfigBuilder::validate`].
+///
+/// Example:
+/// ```rust
+/// use fregate::{AppConfig, Validate, ValidationErrors};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Private {
+///     workers: usize,
+/// }
+///
+/// impl Validate for Private {
+///     fn validate(&self, errors: &mut ValidationErrors) {
+///         if self.workers == 0 {
+///             errors.add("workers", "must be positive");
+///         }
+///     }
+/// }
+///
+/// let config = AppConfig::<Private>::builder()
+///     .add_default()
+///     .add_str("workers = 0", fregate::config::FileFormat::Toml)
+///     .validate()
+///     .build();
+///
+/// assert!(config.is_err());
+/// ```
+pub trait Validate {
+    /// Adds found problems to `errors`, key is a dot separated path from the root of configuration
+    /// as `private` is deserialized from the root.
+    fn validate(&self, errors: &mut ValidationErrors);
+}
+
+impl Validate for Empty {
+    fn validate(&self, _errors: &mut ValidationErrors) {}
+}
+
+/// Single configuration problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// dot separated key path, e.g. `server.http.tcp.keepalive`.
+    pub key: String,
+    /// source value came from, e.g. `default`, `file:./app.yaml` or `env:OTEL`.
+    pub source: Option<String>,
+    /// what is wrong with the value.
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.key)?;
+        if let Some(source) = &self.source {
+            write!(f, " from `{source}`")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// All problems found while validating configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors(Vec<ValidationError>);
+
+impl ValidationErrors {
+    /// Adds problem for dot separated key path.
+    pub fn add(&mut self, key: &str, message: impl Into<String>) {
+        self.0.push(ValidationError {
+            key: key.to_owned(),
+            source: None,
+            message: message.into(),
+        });
+    }
+
+    /// Returns true if no problems were found.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns iterator over found problems.
+    pub fn iter(&self) -> impl Iterator<Item = &ValidationError> {
+        self.0.iter()
+    }
+
+    /// Fills in sources of values.
+    pub(crate) fn with_sources(mut self, effective_cfg: &EffectiveConfig) -> Self {
+        for error in &mut self.0 {
+            let pointer = format!("/{}", error.key.replace('.', "/"));
+            error.source = effective_cfg.source(&pointer).map(ToOwned::to_owned);
+        }
+        self
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} configuration error(s)", self.0.len())?;
+        for error in &self.0 {
+            write!(f, "; {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Checks built-in keys of merged configuration value, used in strict mode.
+pub(crate) fn validate_strict(config: &Value, errors: &mut ValidationErrors) {
+    for key in KEYS {
+        let pointer = format!("/{}", key.key.replace('.', "/"));
+
+        match config.pointer(&pointer) {
+            Some(value) => {
+                if let Err(message) = check(value, key.kind) {
+                    errors.add(key.key, message);
+                }
+            }
+            None if key.required => errors.add(key.key, "is required"),
+            None => {}
+        }
+    }
+
+    let flag = |pointer| config.pointer(pointer).and_then(Value::as_bool);
+    if flag("/server/http/http1/only") == Some(true)
+        && flag("/server/http/http2/only") == Some(true)
+    {
+        errors.add(
+            "server.http.http2.only",
+            "`http1.only` and `http2.only` can't be set at the same time",
+        );
+    }
+}
+
+fn check(value: &Value, kind: ValueKind) -> Result<(), String> {
+    fn typed<T: DeserializeOwned>(value: &Value, expected: &str) -> Result<(), String> {
+        serde_json::from_value::<T>(value.clone())
+            .map(|_| ())
+            .map_err(|_| format!("expected {expected}, got `{value}`"))
+    }
+
+    match kind {
+        ValueKind::Bool => typed::<bool>(value, "boolean"),
+        ValueKind::U16 => typed::<u16>(value, "integer in range 0..=65535"),
+        ValueKind::U32 => typed::<u32>(value, "non-negative 32-bit integer"),
+        ValueKind::U64 => typed::<u64>(value, "non-negative integer"),
+        ValueKind::Usize => typed::<usize>(value, "non-negative integer"),
+        ValueKind::String => typed::<String>(value, "string"),
+        ValueKind::IpAddr => typed::<IpAddr>(value, "IP address"),
+        ValueKind::IpList => {
+            let list = value
+                .as_str()
+                .ok_or_else(|| format!("expected comma separated IP addresses, got `{value}`"))?;
+            let invalid = list
+                .split(',')
+                .map(str::trim)
+                .filter(|ip| ip.parse::<IpNet>().is_err() && ip.parse::<IpAddr>().is_err())
+                .collect::<Vec<_>>();
+
+            if invalid.is_empty() {
+                Ok(())
+            } else {
+                Err(format!("invalid IP addresses: {invalid:?}"))
+            }
+        }
+        ValueKind::Level => {
+            let level = value
+                .as_str()
+                .ok_or_else(|| format!("expected filter directives, got `{value}`"))?;
+            EnvFilter::from_str(level)
+                .map(|_| ())
+                .map_err(|err| format!("invalid filter directives `{level}`: {err}"))
+        }
+        ValueKind::Duration => typed::<Millis>(value, "duration like `500ms`, `30s` or `1h30m`"),
+        ValueKind::ByteSize => typed::<ByteSize>(value, "byte size like `512KB` or `100MiB`"),
+        ValueKind::Count => typed::<Count>(value, "count like `10000` or `10k`"),
+        ValueKind::Endpoint => match value.as_str() {
+            Some(endpoint) if endpoint.starts_with('/') => Ok(()),
+            _ => Err(format!("expected path starting with `/`, got `{value}`")),
+        },
+    }
+}
+
+#[cfg(test)]
+mod validation_test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_all_errors() {
+        let config = json!({
+            "host": "0.0.0.0",
+            "port": 70000,
+            "service": {"name": "svc"},
+            "component": {"name": "cmp", "version": "1"},
+            "log": {"level": "info,[="},
+            "trace": {"level": "info"},
+            "server": {
+                "metrics": {"update_interval": 1000},
+                "tls": {"handshake_timeout": 1000},
+                "http": {"http1": {"only": true}, "http2": {"only": true}},
+            },
+            "management": {"endpoints": {"live": "live"}},
+        });
+
+        let mut errors = ValidationErrors::default();
+        validate_strict(&config, &mut errors);
+
+        let keys = errors
+            .iter()
+            .map(|err| err.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            [
+                "port",
+                "log.level",
+                "management.endpoints.live",
+                "server.http.http2.only"
+            ]
+        );
+    }
+}
@@ -0,0 +1,394 @@
+use crate::configuration::args::Args;
+use crate::configuration::profile::active_profile_files;
+use crate::configuration::source::ConfigSource;
+use crate::error::{Error, Result};
+use crate::observability::{
+    reload_headers_filter, set_reloaded_levels, LOG_LAYER_HANDLE, OTLP_LAYER_HANDLE,
+};
+use crate::{AppConfig, AppConfigBuilder, Validate};
+use config::FileFormat;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tokio::time::MissedTickBehavior;
+use tracing_subscriber::EnvFilter;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const FILE_EXTENSIONS: [&str; 7] = ["toml", "json", "yaml", "yml", "ini", "ron", "json5"];
+
+/// Owned copy of [`ConfigSource`] to rebuild configuration from.
+#[derive(Debug, Clone)]
+enum OwnedSource {
+    String(String, FileFormat),
+    File(String),
+    EnvPrefix(String),
+    Args,
+}
+
+impl OwnedSource {
+    fn as_source(&self) -> ConfigSource<'_> {
+        match self {
+            OwnedSource::String(str, format) => ConfigSource::String(str, *format),
+            OwnedSource::File(path) => ConfigSource::File(path),
+            OwnedSource::EnvPrefix(prefix) => ConfigSource::EnvPrefix(prefix),
+            OwnedSource::Args => ConfigSource::Args,
+        }
+    }
+}
+
+impl From<ConfigSource<'_>> for OwnedSource {
+    fn from(source: ConfigSource<'_>) -> Self {
+        match source {
+            ConfigSource::String(str, format) => OwnedSource::String(str.to_owned(), format),
+            ConfigSource::File(path) => OwnedSource::File(path.to_owned()),
+            ConfigSource::EnvPrefix(prefix) => OwnedSource::EnvPrefix(prefix.to_owned()),
+            ConfigSource::Args => OwnedSource::Args,
+        }
+    }
+}
+
+/// Rebuilds [`AppConfig`] from the same sources when any of [`ConfigSource::File`], `--config` or profile files changes or process receives `SIGHUP`.\
+/// New config is published through [`watch::Receiver`], log and trace levels are re-applied to
+/// [`LOG_LAYER_HANDLE`] and [`OTLP_LAYER_HANDLE`] and [`crate::observability::headers_filter`] is replaced or removed.\
+/// If new config fails to load, error is logged and previous config is kept.
+/// Same checks are applied on each reload as on initial load, see [`ConfigWatcher::strict`] and [`ConfigWatcher::start_validated`].
+///
+/// Example:
+/// ```no_run
+/// use fregate::{bootstrap, tokio, AppConfig, Application, ConfigSource, ConfigWatcher, Empty};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let sources = [ConfigSource::File("./app.yaml"), ConfigSource::EnvPrefix("APP")];
+///     let config: AppConfig = bootstrap(sources.clone()).unwrap();
+///
+///     let mut updates = ConfigWatcher::new(sources).start::<Empty>().unwrap();
+///     tokio::spawn(async move {
+///         while updates.changed().await.is_ok() {
+///             let config = updates.borrow().clone();
+///             println!("New log level: {}", config.observability_cfg.logger_config.log_level);
+///         }
+///     });
+///
+///     Application::new(config).serve().await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigWatcher {
+    sources: Vec<OwnedSource>,
+    poll_interval: Duration,
+    sighup: bool,
+    strict: bool,
+}
+
+impl ConfigWatcher {
+    /// Creates watcher for given sources, default config and `OTEL` prefixed env variables are added same way as in [`AppConfig::load_from`].
+    pub fn new<'a>(sources: impl IntoIterator<Item = ConfigSource<'a>>) -> Self {
+        Self {
+            sources: sources.into_iter().map(OwnedSource::from).collect(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            sighup: true,
+            strict: false,
+        }
+    }
+
+    /// Sets how often files modification time is checked. By default `5s`.
+    #[must_use]
+    pub fn poll_interval(self, poll_interval: Duration) -> Self {
+        Self {
+            poll_interval,
+            ..self
+        }
+    }
+
+    /// Enables or disables reload on `SIGHUP`. By default enabled, has no effect on non-unix platforms.
+    #[must_use]
+    pub fn sighup(self, sighup: bool) -> Self {
+        Self { sighup, ..self }
+    }
+
+    /// Enables strict mode for initial load and every reload, see [`AppConfigBuilder::strict`]. By default disabled.
+    #[must_use]
+    pub fn strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// Loads config and spawns task watching for changes, task stops when all receivers are dropped.\
+    /// Must be called within tokio runtime.
+    pub fn start<ConfigExt>(self) -> Result<watch::Receiver<Arc<AppConfig<ConfigExt>>>>
+    where
+        ConfigExt: Debug + DeserializeOwned + Send + Sync + 'static,
+    {
+        self.start_with(|builder| builder)
+    }
+
+    /// Same as [`ConfigWatcher::start`], but calls [`Validate::validate`] on `private` section on initial load and every reload,
+    /// see [`AppConfigBuilder::validate`].
+    pub fn start_validated<ConfigExt>(self) -> Result<watch::Receiver<Arc<AppConfig<ConfigExt>>>>
+    where
+        ConfigExt: Debug + DeserializeOwned + Validate + Send + Sync + 'static,
+    {
+        self.start_with(AppConfigBuilder::validate)
+    }
+
+    fn start_with<ConfigExt>(
+        self,
+        customize: fn(AppConfigBuilder<ConfigExt>) -> AppConfigBuilder<ConfigExt>,
+    ) -> Result<watch::Receiver<Arc<AppConfig<ConfigExt>>>>
+    where
+        ConfigExt: Debug + DeserializeOwned + Send + Sync + 'static,
+    {
+        let config = self.load(customize)?;
+        let (sender, receiver) = watch::channel(Arc::new(config));
+
+        #[cfg(unix)]
+        let hangup = if self.sighup {
+            use tokio::signal::unix::{signal, SignalKind};
+            Some(signal(SignalKind::hangup())?)
+        } else {
+            None
+        };
+
+        tokio::spawn(async move {
+            #[cfg(unix)]
+            let mut hangup = hangup;
+            let mut stamps = self.file_stamps();
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            loop {
+                #[cfg(unix)]
+                let sighup = async {
+                    match hangup.as_mut() {
+                        Some(hangup) => hangup.recv().await,
+                        None => std::future::pending().await,
+                    }
+                };
+                #[cfg(not(unix))]
+                let sighup = std::future::pending::<Option<()>>();
+
+                let reason = tokio::select! {
+                    _ = sender.closed() => return,
+                    _ = interval.tick() => {
+                        let current = self.file_stamps();
+                        if current == stamps {
+                            continue;
+                        }
+                        stamps = current;
+                        "file change"
+                    },
+                    Some(_) = sighup => "SIGHUP",
+                };
+
+                match self.load(customize).and_then(|config| {
+                    apply(&config)?;
+                    Ok(config)
+                }) {
+                    Ok(config) => {
+                        let masked = config
+                            .effective_cfg
+                            .masked(&config.management_cfg.config_dump.sanitize);
+                        tracing::info!("Configuration reloaded on {reason}: `{masked}`.");
+                        sender.send_replace(Arc::new(config));
+                    }
+                    Err(err) => tracing::error!(
+                        "Failed to reload configuration on {reason}, previous configuration is kept: {err}"
+                    ),
+                }
+            }
+        });
+
+        Ok(receiver)
+    }
+
+    fn load<ConfigExt>(
+        &self,
+        customize: fn(AppConfigBuilder<ConfigExt>) -> AppConfigBuilder<ConfigExt>,
+    ) -> Result<AppConfig<ConfigExt>>
+    where
+        ConfigExt: Debug + DeserializeOwned,
+    {
+        let builder =
+            AppConfigBuilder::from_sources(self.sources.iter().map(OwnedSource::as_source))
+                .strict(self.strict);
+
+        customize(builder).build()
+    }
+
+    fn file_stamps(&self) -> Vec<Option<SystemTime>> {
+        active_profile_files()
+            .into_iter()
+            .chain(self.sources.iter().flat_map(|source| match source {
+                OwnedSource::File(path) => vec![path.clone()],
+                OwnedSource::Args => Args::config_files(),
+                _ => vec![],
+            }))
+            .map(|path| modified(&path))
+            .collect()
+    }
+}
+
+/// Returns modification time of file, path might be given without extension same as for [`ConfigSource::File`].
+fn modified(path: &str) -> Option<SystemTime> {
+    let path = Path::new(path);
+    let candidates = std::iter::once(path.to_path_buf())
+        .chain(FILE_EXTENSIONS.iter().map(|ext| path.with_extension(ext)));
+
+    candidates
+        .filter(|candidate: &PathBuf| candidate.is_file())
+        .find_map(|candidate| candidate.metadata().and_then(|meta| meta.modified()).ok())
+}
+
+/// Re-applies log and trace levels and headers filter, nothing is changed if any of levels is invalid.
+fn apply<ConfigExt>(config: &AppConfig<ConfigExt>) -> Result<()> {
+    let observability_cfg = &config.observability_cfg;
+    let parse = |level: &str| {
+        EnvFilter::from_str(level)
+            .map_err(|err| Error::CustomError(format!("Invalid level `{level}`: {err}")))
+    };
+    let log_filter = parse(&observability_cfg.logger_config.log_level)?;
+    let trace_filter = parse(&observability_cfg.trace_level)?;
+
+    if let Some(handle) = LOG_LAYER_HANDLE.get() {
+        handle
+            .reload(log_filter)
+            .map_err(|err| Error::CustomError(err.to_string()))?;
+    }
+    if let Some(handle) = OTLP_LAYER_HANDLE.get() {
+        handle
+            .reload(trace_filter)
+            .map_err(|err| Error::CustomError(err.to_string()))?;
+    }
+    set_reloaded_levels(
+        &observability_cfg.logger_config.log_level,
+        &observability_cfg.trace_level,
+    );
+    reload_headers_filter(observability_cfg.logger_config.headers_filter.clone());
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod watcher_test {
+    use super::*;
+    use crate::observability::{Filter, HeadersFilter};
+    use crate::Empty;
+    use std::sync::OnceLock;
+    use tokio::sync::{Mutex, MutexGuard};
+
+    /// Tests reloading global filters must not run concurrently.
+    async fn reload_lock() -> MutexGuard<'static, ()> {
+        static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+        LOCK.get_or_init(Mutex::default).lock().await
+    }
+
+    #[tokio::test]
+    async fn reloads_on_file_change_and_keeps_previous_on_error() {
+        let _lock = reload_lock().await;
+        let dir = std::env::temp_dir().join(format!("fregate-watcher-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("app.toml");
+        std::fs::write(&path, "port = 1111").unwrap();
+
+        let path_str = path.to_str().unwrap();
+        let mut receiver = ConfigWatcher::new([ConfigSource::File(path_str)])
+            .poll_interval(Duration::from_millis(10))
+            .sighup(false)
+            .start::<Empty>()
+            .unwrap();
+        assert_eq!(receiver.borrow().port, 1111);
+
+        // make sure modification time differs on file systems with coarse timestamps.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        std::fs::write(&path, "port = 2222").unwrap();
+        bump_modified(&path);
+        tokio::time::timeout(Duration::from_secs(5), receiver.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receiver.borrow().port, 2222);
+
+        std::fs::write(&path, "port = \"invalid\"").unwrap();
+        bump_modified(&path);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!receiver.has_changed().unwrap());
+        assert_eq!(receiver.borrow().port, 2222);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Workers {
+        workers: usize,
+    }
+
+    impl Validate for Workers {
+        fn validate(&self, errors: &mut crate::ValidationErrors) {
+            if self.workers == 0 {
+                errors.add("workers", "must be positive");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn reload_applies_strict_and_validate_checks() {
+        let _lock = reload_lock().await;
+        let dir =
+            std::env::temp_dir().join(format!("fregate-watcher-strict-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("app.toml");
+        std::fs::write(&path, "workers = 1").unwrap();
+
+        let path_str = path.to_str().unwrap();
+        let receiver = ConfigWatcher::new([ConfigSource::File(path_str)])
+            .poll_interval(Duration::from_millis(10))
+            .sighup(false)
+            .strict(true)
+            .start_validated::<Workers>()
+            .unwrap();
+        assert_eq!(receiver.borrow().private.workers, 1);
+
+        for invalid in [
+            "workers = 0",
+            "workers = 2\nmanagement.endpoints.health = \"health\"",
+        ] {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            std::fs::write(&path, invalid).unwrap();
+            bump_modified(&path);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            assert!(!receiver.has_changed().unwrap(), "{invalid}");
+            assert_eq!(receiver.borrow().private.workers, 1);
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn apply_removes_headers_filter_missing_in_config() {
+        let _lock = reload_lock().await;
+        let mut config = AppConfig::default();
+        config.observability_cfg.logger_config.headers_filter = Some(HeadersFilter {
+            include: Filter::All,
+            exclude: Filter::All,
+            sanitize: Filter::All,
+        });
+        apply(&config).unwrap();
+        assert!(crate::observability::headers_filter().is_some());
+
+        config.observability_cfg.logger_config.headers_filter = None;
+        apply(&config).unwrap();
+        assert!(crate::observability::headers_filter().is_none());
+    }
+
+    fn bump_modified(path: &Path) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        let modified = file.metadata().unwrap().modified().unwrap();
+        file.set_modified(modified + Duration::from_secs(1))
+            .unwrap();
+    }
+}
@@ -26,6 +26,9 @@ pub enum Error {
     /// Error returned by serde_json crate
     #[error("Got SerdeError: `{0}`")]
     SerdeError(#[from] serde_json::Error),
+    /// Error returned when configuration fails validation, contains all found problems
+    #[error("Got ValidationError: `{0}`")]
+    ValidationError(#[from] crate::configuration::ValidationErrors),
     /// Custom fregate Error
     #[error("Got CustomError: `{0}`")]
     CustomError(String),
@@ -36,7 +39,6 @@ pub enum Error {
     #[error("Got OpentelemetryError: `{0}`")]
     OpentelemetryError(#[from] opentelemetry::global::Error),
     /// tokio JoinHandle error
-    #[cfg(feature = "tls")]
     #[error("Got JoinHandleError: `{0}`")]
     JoinHandleError(#[from] tokio::task::JoinError),
     /// TLS HandshakeTimeout
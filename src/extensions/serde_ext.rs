@@ -12,6 +12,15 @@ pub trait DeserializeExt {
     where
         T: Deserialize<'de>,
         E: Error;
+
+    /// find value by given pointer and try to deserialize, returns None if value is missing
+    fn pointer_and_deserialize_optional<'de, T, E>(
+        &'de self,
+        pointer: &'static str,
+    ) -> Result<Option<T>, E>
+    where
+        T: Deserialize<'de>,
+        E: Error;
 }
 
 #[sealed]
@@ -27,4 +36,19 @@ impl DeserializeExt for Value {
 
         T::deserialize(raw_ret).map_err(E::custom)
     }
+
+    fn pointer_and_deserialize_optional<'de, T, E>(
+        &'de self,
+        pointer: &'static str,
+    ) -> Result<Option<T>, E>
+    where
+        T: Deserialize<'de>,
+        E: Error,
+    {
+        self.pointer(pointer)
+            .map(|value| {
+                T::deserialize(value).map_err(|err| E::custom(format!("{pointer}: {err}")))
+            })
+            .transpose()
+    }
 }
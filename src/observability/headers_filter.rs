@@ -1,15 +1,18 @@
 //! [`HeadersFilter`] definition
 use crate::extensions::DeserializeExt;
+use arc_swap::ArcSwapOption;
 use serde::{Deserialize, Deserializer};
 use serde_json::Value;
 use std::collections::HashSet;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 const SANITIZE_PTR: &str = "/sanitize";
 const INCLUDE_PTR: &str = "/include";
 const EXCLUDE_PTR: &str = "/exclude";
 
 /// This is uninitialised unless you call [`crate::bootstrap()`] or [`crate::observability::init_tracing()`] functions.
+/// Holds initial filter only and is not updated on configuration reload, see [`crate::ConfigWatcher`],
+/// use [`headers_filter`] to get filter currently in use.
 /// If initialised but env variables are not set fregate will include all headers.
 /// Expects string values separated with ',' or standalone "*" character meaning: all.
 /// Example:
@@ -22,6 +25,33 @@ const EXCLUDE_PTR: &str = "/exclude";
 /// Include all headers except for "authorization" and sanitize "password,login,client_id" headers.
 pub static HEADERS_FILTER: OnceLock<HeadersFilter> = OnceLock::new();
 
+/// Filter currently in use, initial [`HEADERS_FILTER`] replaced on configuration reload.
+/// Read on every request, so it is swapped atomically instead of being guarded by lock.
+static CURRENT_HEADERS_FILTER: ArcSwapOption<HeadersFilter> = ArcSwapOption::const_empty();
+
+/// Returns filter currently in use, [`None`] if filter is uninitialised or removed on configuration reload.
+pub fn headers_filter() -> Option<Arc<HeadersFilter>> {
+    CURRENT_HEADERS_FILTER.load_full()
+}
+
+/// Calls `f` with current filter, [`None`] if filter is uninitialised.
+pub(crate) fn with_headers_filter<R>(f: impl FnOnce(Option<&HeadersFilter>) -> R) -> R {
+    let current = CURRENT_HEADERS_FILTER.load();
+    f(current.as_deref())
+}
+
+/// Sets initial filter if [`HEADERS_FILTER`] is uninitialised.
+pub(crate) fn set_headers_filter(filter: HeadersFilter) {
+    if HEADERS_FILTER.set(filter.clone()).is_ok() {
+        CURRENT_HEADERS_FILTER.store(Some(Arc::new(filter)));
+    }
+}
+
+/// Replaces filter currently in use, [`None`] removes it.
+pub(crate) fn reload_headers_filter(filter: Option<HeadersFilter>) {
+    CURRENT_HEADERS_FILTER.store(filter.map(Arc::new));
+}
+
 /// Headers filter options
 #[derive(Debug, Clone)]
 pub enum Filter {
@@ -67,7 +97,7 @@ impl<'de> Deserialize<'de> for HeadersFilter {
     }
 }
 
-fn from_str_to_filter(str: Option<String>) -> Filter {
+pub(crate) fn from_str_to_filter(str: Option<String>) -> Filter {
     str.map_or(Filter::Set(HashSet::default()), |str| {
         let str = str.trim();
 
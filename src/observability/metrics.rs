@@ -6,8 +6,13 @@ pub(crate) mod sys_info;
 #[cfg(feature = "tokio-metrics")]
 pub mod tokio_metrics;
 
+use crate::application::supervisor::{RestartPolicy, Supervisor};
+use crate::configuration::ObservabilityConfig;
 use crate::error::Result;
 use crate::observability::metrics::recorder::{get_handle, get_recorder};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static METRICS_INITIALISED: AtomicBool = AtomicBool::new(false);
 
 /// Return rendered metrics.
 /// By default fregate sets `/metrics` endpoint for your [`Application]` which uses [`metrics_exporter_prometheus::PrometheusHandle::render`] fn to get currently available metrics.
@@ -33,5 +38,30 @@ pub fn init_metrics(cgroup_metrics: bool) -> Result<()> {
         sys_info::register_sys_metrics();
     }
 
+    METRICS_INITIALISED.store(true, Ordering::Release);
     Ok(())
 }
+
+/// Registers tasks updating runtime, cgroup or process metrics in [`Supervisor`] if metrics were initialised with [`init_metrics`].
+pub(crate) fn register_metrics_tasks(supervisor: &Supervisor, config: &ObservabilityConfig) {
+    if !METRICS_INITIALISED.load(Ordering::Acquire) {
+        return;
+    }
+
+    let interval = config.metrics_update_interval;
+
+    #[cfg(feature = "tokio-metrics")]
+    supervisor.register("tokio_metrics", RestartPolicy::default(), move |token| {
+        tokio_metrics::tokio_metrics_task(interval, token)
+    });
+
+    if config.cgroup_metrics {
+        supervisor.register("cgroup_metrics", RestartPolicy::default(), move |token| {
+            cgroupv2::cgroup_metrics_task(interval, token)
+        });
+    } else {
+        supervisor.register("sys_metrics", RestartPolicy::default(), move |token| {
+            sys_info::sys_metrics_task(interval, token)
+        });
+    }
+}
@@ -5,6 +5,7 @@ use std::num::ParseIntError;
 use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum MaxValue {
@@ -127,12 +128,16 @@ fn get_cgroup_path() -> Result<String, Box<dyn std::error::Error>> {
     }
 }
 
-pub fn init_cgroup_metrics(metrics_update_ms: Duration) {
+/// Updates cgroup metrics every `metrics_update_ms` until `token` is cancelled.
+pub(crate) async fn cgroup_metrics_task(
+    metrics_update_ms: Duration,
+    token: CancellationToken,
+) -> Result<(), Infallible> {
     let cgroup_path = match get_cgroup_path() {
         Ok(path) => path,
         Err(err) => {
             tracing::error!("Could not init cgroup metrics: {err}");
-            return;
+            return Ok(());
         }
     };
 
@@ -142,43 +147,44 @@ pub fn init_cgroup_metrics(metrics_update_ms: Duration) {
     let cpu_max = format!("{cgroup_path}/cpu.max");
     let cpu_stat = format!("{cgroup_path}/cpu.stat");
 
-    tokio::task::spawn(async move {
-        loop {
-            let num_cpu = num_cpus::get() as u64;
-            let cgroup_mem_used = read_into::<u64>(memory_current_path.as_str());
-            let cgroup_mem_available = read_into::<MaxValue>(cgroup_mem_available.as_str());
-            let cpu_weight = read_into::<u64>(cpu_weight.as_str());
-            let cpu_max = read_into::<CpuMax>(cpu_max.as_str());
-            let cpu_stat = read_into::<CpuStat>(cpu_stat.as_str());
-
-            if let Some(CpuStat {
-                usage_usec,
-                user_usec,
-                system_usec,
-                nr_periods,
-                nr_throttled,
-                throttled_usec,
-            }) = cpu_stat
-            {
-                set_gauge_from_option("cpu.stat.usage_usec", usage_usec);
-                set_gauge_from_option("cpu.stat.user_usec", user_usec);
-                set_gauge_from_option("cpu.stat.system_usec", system_usec);
-                set_gauge_from_option("cpu.stat.nr_periods", nr_periods);
-                set_gauge_from_option("cpu.stat.nr_throttled", nr_throttled);
-                set_gauge_from_option("cpu.stat.throttled_usec", throttled_usec);
-            }
-            if let Some(CpuMax { max, period }) = cpu_max {
-                set_gauge_from_max_option("cpu.max.max", max);
-                set_gauge_from_option("cpu.max.period", period);
-            }
-            set_gauge_from_option("cpu.weight", cpu_weight);
-            set_gauge_from_option("num_cpus", Some(num_cpu));
-            set_gauge_from_option("memory_used", cgroup_mem_used);
-            set_gauge_from_max_option("memory_available", cgroup_mem_available);
+    loop {
+        let num_cpu = num_cpus::get() as u64;
+        let cgroup_mem_used = read_into::<u64>(memory_current_path.as_str());
+        let cgroup_mem_available = read_into::<MaxValue>(cgroup_mem_available.as_str());
+        let cpu_weight = read_into::<u64>(cpu_weight.as_str());
+        let cpu_max = read_into::<CpuMax>(cpu_max.as_str());
+        let cpu_stat = read_into::<CpuStat>(cpu_stat.as_str());
 
-            tokio::time::sleep(metrics_update_ms).await;
+        if let Some(CpuStat {
+            usage_usec,
+            user_usec,
+            system_usec,
+            nr_periods,
+            nr_throttled,
+            throttled_usec,
+        }) = cpu_stat
+        {
+            set_gauge_from_option("cpu.stat.usage_usec", usage_usec);
+            set_gauge_from_option("cpu.stat.user_usec", user_usec);
+            set_gauge_from_option("cpu.stat.system_usec", system_usec);
+            set_gauge_from_option("cpu.stat.nr_periods", nr_periods);
+            set_gauge_from_option("cpu.stat.nr_throttled", nr_throttled);
+            set_gauge_from_option("cpu.stat.throttled_usec", throttled_usec);
+        }
+        if let Some(CpuMax { max, period }) = cpu_max {
+            set_gauge_from_max_option("cpu.max.max", max);
+            set_gauge_from_option("cpu.max.period", period);
         }
-    });
+        set_gauge_from_option("cpu.weight", cpu_weight);
+        set_gauge_from_option("num_cpus", Some(num_cpu));
+        set_gauge_from_option("memory_used", cgroup_mem_used);
+        set_gauge_from_max_option("memory_available", cgroup_mem_available);
+
+        tokio::select! {
+            _ = tokio::time::sleep(metrics_update_ms) => {},
+            _ = token.cancelled() => return Ok(()),
+        }
+    }
 }
 
 fn set_gauge_from_option(name: &'static str, v: Option<u64>) {
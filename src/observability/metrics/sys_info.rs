@@ -1,48 +1,54 @@
 use metrics::{describe_gauge, gauge, register_gauge};
+use std::convert::Infallible;
 use std::time::Duration;
 use sysinfo::{Pid, ProcessExt, System, SystemExt};
+use tokio_util::sync::CancellationToken;
 
-pub fn init_sys_metrics(metrics_update_ms: Duration) {
+/// Updates process metrics every `metrics_update_ms` until `token` is cancelled.
+pub(crate) async fn sys_metrics_task(
+    metrics_update_ms: Duration,
+    token: CancellationToken,
+) -> Result<(), Infallible> {
     let u32_pid = std::process::id();
     let pid = Pid::from(u32_pid as usize);
+    let mut system = System::new();
 
-    tokio::task::spawn(async move {
-        let mut system = System::new();
-
-        loop {
+    loop {
+        {
+            #[cfg(target_os = "linux")]
             {
-                #[cfg(target_os = "linux")]
-                {
-                    use crate::observability::proc_limits::{read_process_limits, UnlimitedValue};
+                use crate::observability::proc_limits::{read_process_limits, UnlimitedValue};
 
-                    match read_process_limits(u32_pid) {
-                        Ok(limits) => match limits.max_cpu_limit.soft_limit {
-                            Some(UnlimitedValue::Unlimited) => gauge!("max_cpu_time", -1_f64),
-                            Some(UnlimitedValue::Value(v)) => gauge!("max_cpu_time", v as f64),
-                            _ => {}
-                        },
-                        Err(err) => {
-                            tracing::error!("Could not update limits: {err}");
-                        }
+                match read_process_limits(u32_pid) {
+                    Ok(limits) => match limits.max_cpu_limit.soft_limit {
+                        Some(UnlimitedValue::Unlimited) => gauge!("max_cpu_time", -1_f64),
+                        Some(UnlimitedValue::Value(v)) => gauge!("max_cpu_time", v as f64),
+                        _ => {}
+                    },
+                    Err(err) => {
+                        tracing::error!("Could not update limits: {err}");
                     }
                 }
+            }
 
-                system.refresh_memory();
-                system.refresh_process(pid);
-                let process = system.process(pid);
-
-                if let Some(process) = process {
-                    gauge!("memory_used", process.memory() as f64);
-                    gauge!("cpu_used", process.cpu_usage() as f64);
-                }
+            system.refresh_memory();
+            system.refresh_process(pid);
+            let process = system.process(pid);
 
-                gauge!("num_cpus", num_cpus::get() as f64);
-                gauge!("memory_available", system.total_memory() as f64);
+            if let Some(process) = process {
+                gauge!("memory_used", process.memory() as f64);
+                gauge!("cpu_used", process.cpu_usage() as f64);
             }
 
-            tokio::time::sleep(metrics_update_ms).await;
+            gauge!("num_cpus", num_cpus::get() as f64);
+            gauge!("memory_available", system.total_memory() as f64);
         }
-    });
+
+        tokio::select! {
+            _ = tokio::time::sleep(metrics_update_ms) => {},
+            _ = token.cancelled() => return Ok(()),
+        }
+    }
 }
 
 pub(crate) fn register_sys_metrics() {
@@ -71,3 +77,21 @@ pub(crate) fn register_sys_metrics() {
         register_gauge!("max_cpu_time");
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod sys_info_test {
+    use super::*;
+
+    #[tokio::test]
+    async fn stops_on_cancel() {
+        let token = CancellationToken::new();
+        let task = tokio::spawn(sys_metrics_task(Duration::from_secs(60), token.clone()));
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+
+        let stopped = tokio::time::timeout(Duration::from_secs(1), task).await;
+        assert!(stopped.unwrap().unwrap().is_ok());
+    }
+}
@@ -3,128 +3,139 @@
 use metrics::{
     absolute_counter, describe_counter, describe_gauge, gauge, register_counter, register_gauge,
 };
+use std::convert::Infallible;
 use std::time::Duration;
 use tokio::runtime::Handle;
 use tokio_metrics::{RuntimeMetrics, RuntimeMonitor};
+use tokio_util::sync::CancellationToken;
 
 /// Initialise key [`metrics`](https://docs.rs/tokio-metrics/latest/tokio_metrics/struct.TaskMetrics.html) of tokio tasks.\
+/// Spawns detached task updating metrics every `metrics_update_interval`.
+/// [`crate::Application`] already supervises the same task once metrics are initialised with [`init_metrics`](crate::observability::init_metrics),
+/// use this fn only when metrics are needed without [`crate::Application`].\
 /// Example:
 /// ```no_run
-/// use fregate::Application;
-/// use fregate::configuration::AppConfig;
 /// use fregate::observability::init_metrics;
 /// use fregate::observability::tokio_metrics::init_tokio_metrics_task;
 /// use std::time::Duration;
 ///
 /// #[tokio::main]
 /// async fn main() {
-///     
-/// init_metrics(false).expect("Failed to initialise PrometheusRecorder");
+///     init_metrics(false).expect("Failed to initialise PrometheusRecorder");
 ///     init_tokio_metrics_task(Duration::from_millis(500));
-///
-///     Application::new(AppConfig::default())
-///         .serve()
-///         .await
-///         .unwrap()
 /// }
 /// ```
 pub fn init_tokio_metrics_task(metrics_update_interval: Duration) {
+    tokio::task::spawn(tokio_metrics_task(
+        metrics_update_interval,
+        CancellationToken::new(),
+    ));
+}
+
+/// Updates tokio runtime metrics every `metrics_update_interval` until `token` is cancelled.
+pub(crate) async fn tokio_metrics_task(
+    metrics_update_interval: Duration,
+    token: CancellationToken,
+) -> Result<(), Infallible> {
     let handle = Handle::current();
     let runtime_monitor = RuntimeMonitor::new(&handle);
 
-    tokio::task::spawn(async move {
-        for RuntimeMetrics {
-            workers_count,
-            total_park_count,
-            max_park_count,
-            min_park_count,
-            total_noop_count,
-            max_noop_count,
-            min_noop_count,
-            total_steal_count,
-            max_steal_count,
-            min_steal_count,
-            total_steal_operations,
-            max_steal_operations,
-            min_steal_operations,
-            num_remote_schedules,
-            total_local_schedule_count,
-            max_local_schedule_count,
-            min_local_schedule_count,
-            total_overflow_count,
-            max_overflow_count,
-            min_overflow_count,
-            total_polls_count,
-            max_polls_count,
-            min_polls_count,
-            total_busy_duration,
-            max_busy_duration,
-            min_busy_duration,
-            injection_queue_depth,
-            total_local_queue_depth,
-            max_local_queue_depth,
-            min_local_queue_depth,
-            elapsed,
-            budget_forced_yield_count,
-            io_driver_ready_count,
-            ..
-        } in runtime_monitor.intervals()
-        {
-            absolute_counter!(
-                "workers_count",
-                workers_count.try_into().unwrap_or(u64::MAX)
-            );
-            absolute_counter!("total_park_count", total_park_count);
-            absolute_counter!("max_park_count", max_park_count);
-            absolute_counter!("min_park_count", min_park_count);
-            absolute_counter!("total_noop_count", total_noop_count);
-            absolute_counter!("max_noop_count", max_noop_count);
-            absolute_counter!("min_noop_count", min_noop_count);
-            absolute_counter!("total_steal_count", total_steal_count);
-            absolute_counter!("max_steal_count", max_steal_count);
-            absolute_counter!("min_steal_count", min_steal_count);
-            absolute_counter!("max_steal_operations", max_steal_operations);
-            absolute_counter!("min_steal_operations", min_steal_operations);
-            absolute_counter!("num_remote_schedules", num_remote_schedules);
-            absolute_counter!("total_local_schedule_count", total_local_schedule_count);
-            absolute_counter!("max_local_schedule_count", max_local_schedule_count);
-            absolute_counter!("min_local_schedule_count", min_local_schedule_count);
-            absolute_counter!("total_overflow_count", total_overflow_count);
-            absolute_counter!("max_overflow_count", max_overflow_count);
-            absolute_counter!("min_overflow_count", min_overflow_count);
-            absolute_counter!("total_polls_count", total_polls_count);
-            absolute_counter!("max_polls_count", max_polls_count);
-            absolute_counter!("min_polls_count", min_polls_count);
-            absolute_counter!("total_busy_duration", total_busy_duration.as_secs());
-            absolute_counter!("max_busy_duration", max_busy_duration.as_secs());
-            absolute_counter!("min_busy_duration", min_busy_duration.as_secs());
-            gauge!(
-                "total_steal_operations",
-                usize_to_f64_saturated(total_steal_operations.try_into().unwrap_or(usize::MAX))
-            );
-            gauge!(
-                "injection_queue_depth",
-                usize_to_f64_saturated(injection_queue_depth)
-            );
-            gauge!(
-                "total_local_queue_depth",
-                usize_to_f64_saturated(total_local_queue_depth)
-            );
-            gauge!(
-                "max_local_queue_depth",
-                usize_to_f64_saturated(max_local_queue_depth)
-            );
-            gauge!(
-                "min_local_queue_depth",
-                usize_to_f64_saturated(min_local_queue_depth)
-            );
-            absolute_counter!("elapsed", elapsed.as_secs());
-            absolute_counter!("budget_forced_yield_count", budget_forced_yield_count);
-            absolute_counter!("io_driver_ready_count", io_driver_ready_count);
+    for RuntimeMetrics {
+        workers_count,
+        total_park_count,
+        max_park_count,
+        min_park_count,
+        total_noop_count,
+        max_noop_count,
+        min_noop_count,
+        total_steal_count,
+        max_steal_count,
+        min_steal_count,
+        total_steal_operations,
+        max_steal_operations,
+        min_steal_operations,
+        num_remote_schedules,
+        total_local_schedule_count,
+        max_local_schedule_count,
+        min_local_schedule_count,
+        total_overflow_count,
+        max_overflow_count,
+        min_overflow_count,
+        total_polls_count,
+        max_polls_count,
+        min_polls_count,
+        total_busy_duration,
+        max_busy_duration,
+        min_busy_duration,
+        injection_queue_depth,
+        total_local_queue_depth,
+        max_local_queue_depth,
+        min_local_queue_depth,
+        elapsed,
+        budget_forced_yield_count,
+        io_driver_ready_count,
+        ..
+    } in runtime_monitor.intervals()
+    {
+        absolute_counter!(
+            "workers_count",
+            workers_count.try_into().unwrap_or(u64::MAX)
+        );
+        absolute_counter!("total_park_count", total_park_count);
+        absolute_counter!("max_park_count", max_park_count);
+        absolute_counter!("min_park_count", min_park_count);
+        absolute_counter!("total_noop_count", total_noop_count);
+        absolute_counter!("max_noop_count", max_noop_count);
+        absolute_counter!("min_noop_count", min_noop_count);
+        absolute_counter!("total_steal_count", total_steal_count);
+        absolute_counter!("max_steal_count", max_steal_count);
+        absolute_counter!("min_steal_count", min_steal_count);
+        absolute_counter!("max_steal_operations", max_steal_operations);
+        absolute_counter!("min_steal_operations", min_steal_operations);
+        absolute_counter!("num_remote_schedules", num_remote_schedules);
+        absolute_counter!("total_local_schedule_count", total_local_schedule_count);
+        absolute_counter!("max_local_schedule_count", max_local_schedule_count);
+        absolute_counter!("min_local_schedule_count", min_local_schedule_count);
+        absolute_counter!("total_overflow_count", total_overflow_count);
+        absolute_counter!("max_overflow_count", max_overflow_count);
+        absolute_counter!("min_overflow_count", min_overflow_count);
+        absolute_counter!("total_polls_count", total_polls_count);
+        absolute_counter!("max_polls_count", max_polls_count);
+        absolute_counter!("min_polls_count", min_polls_count);
+        absolute_counter!("total_busy_duration", total_busy_duration.as_secs());
+        absolute_counter!("max_busy_duration", max_busy_duration.as_secs());
+        absolute_counter!("min_busy_duration", min_busy_duration.as_secs());
+        gauge!(
+            "total_steal_operations",
+            usize_to_f64_saturated(total_steal_operations.try_into().unwrap_or(usize::MAX))
+        );
+        gauge!(
+            "injection_queue_depth",
+            usize_to_f64_saturated(injection_queue_depth)
+        );
+        gauge!(
+            "total_local_queue_depth",
+            usize_to_f64_saturated(total_local_queue_depth)
+        );
+        gauge!(
+            "max_local_queue_depth",
+            usize_to_f64_saturated(max_local_queue_depth)
+        );
+        gauge!(
+            "min_local_queue_depth",
+            usize_to_f64_saturated(min_local_queue_depth)
+        );
+        absolute_counter!("elapsed", elapsed.as_secs());
+        absolute_counter!("budget_forced_yield_count", budget_forced_yield_count);
+        absolute_counter!("io_driver_ready_count", io_driver_ready_count);
 
-            tokio::time::sleep(metrics_update_interval).await;
+        tokio::select! {
+            _ = tokio::time::sleep(metrics_update_interval) => {},
+            _ = token.cancelled() => return Ok(()),
         }
-    });
+    }
+
+    Ok(())
 }
 
 pub(crate) fn register_metrics() {
@@ -14,10 +14,10 @@ pub use tracing_fields::*;
 pub use writer::*;
 
 use crate::error::Result;
-use crate::observability::HEADERS_FILTER;
+use crate::observability::set_headers_filter;
 use crate::LoggerConfig;
 use opentelemetry::global::set_error_handler;
-use std::sync::OnceLock;
+use std::sync::{OnceLock, PoisonError, RwLock};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::layer::Layered;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -49,7 +49,7 @@ pub type TraceLayerHandle = Handle<EnvFilter, Registry>;
 /// 1. [`log_layer()`] with custom event formatter [`EventFormatter`].\
 /// 2. [`otlp_layer()`].\
 /// 3. Reload filters for both layers: [`OTLP_LAYER_HANDLE`] and [`LOG_LAYER_HANDLE`].\
-/// 4. [`HEADERS_FILTER`] to be used in [`crate::extensions::HeaderFilterExt`].\
+/// 4. [`crate::observability::HEADERS_FILTER`] to be used in [`crate::extensions::HeaderFilterExt`].\
 /// 5. Sets panic hook.\
 /// Uses [`tracing_appender`] crate to do non blocking writes to stdout, so returns [`WorkerGuard`]. Read more here: [`https://docs.rs/tracing-appender/latest/tracing_appender/non_blocking/struct.WorkerGuard.html`]
 #[allow(clippy::too_many_arguments)]
@@ -71,7 +71,7 @@ pub fn init_tracing(
         let _ = OTLP_LAYER_HANDLE.get_or_init(|| otlp_reload);
     }
     if let Some(headers_filter) = logger_config.headers_filter.clone() {
-        let _ = HEADERS_FILTER.get_or_init(|| headers_filter);
+        set_headers_filter(headers_filter);
     }
 
     set_error_handler(|err| {
@@ -98,3 +98,21 @@ fn set_panic_hook() {
         }
     }));
 }
+
+/// Log and trace levels applied on last configuration reload, see [`crate::ConfigWatcher`].
+static RELOADED_LEVELS: RwLock<Option<(String, String)>> = RwLock::new(None);
+
+/// Returns log and trace levels applied on last configuration reload, [`None`] if configuration was not reloaded.
+pub(crate) fn reloaded_levels() -> Option<(String, String)> {
+    RELOADED_LEVELS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+pub(crate) fn set_reloaded_levels(log_level: &str, trace_level: &str) {
+    *RELOADED_LEVELS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) =
+        Some((log_level.to_owned(), trace_level.to_owned()));
+}
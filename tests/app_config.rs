@@ -1,7 +1,8 @@
 mod app_config_tests {
     use config::FileFormat;
-    use fregate::{AppConfig, ConfigSource, Empty};
+    use fregate::{AppConfig, ConfigSource, Empty, HttpConfig, LimitsConfig};
     use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::time::Duration;
 
     #[test]
     fn multiple_config() {
@@ -42,8 +43,29 @@ mod app_config_tests {
         assert_eq!(mngmnt.endpoints.health.as_ref(), "/health");
         assert_eq!(mngmnt.endpoints.ready.as_ref(), "/ready");
         assert_eq!(mngmnt.endpoints.live.as_ref(), "/live");
+        assert_eq!(mngmnt.endpoints.startup.as_ref(), "/startup");
         assert_eq!(mngmnt.endpoints.metrics.as_ref(), "/metrics");
         assert_eq!(mngmnt.endpoints.version.as_ref(), "/version");
+        assert_eq!(mngmnt.endpoints.config.as_ref(), "/config");
+        assert_eq!(mngmnt.endpoints.diagnostics.as_ref(), "/diagnostics");
+        assert_eq!(mngmnt.endpoints.profile.as_ref(), "/debug/pprof/profile");
+        assert_eq!(
+            mngmnt.endpoints.flamegraph.as_ref(),
+            "/debug/pprof/flamegraph"
+        );
+        assert_eq!(mngmnt.endpoints.loglevel.as_ref(), "/loglevel");
+        assert!(!mngmnt.config_dump.enabled);
+        assert!(!mngmnt.auth.is_enabled());
+        assert!(mngmnt.auth.exempt_probes);
+        assert_eq!(mngmnt.host, None);
+        assert_eq!(mngmnt.port, None);
+
+        let shutdown = config.shutdown_cfg;
+        assert_eq!(shutdown.pre_stop_delay, Duration::ZERO);
+        assert_eq!(shutdown.drain_timeout, None);
+
+        assert_eq!(config.http_cfg, HttpConfig::default());
+        assert_eq!(config.limits_cfg, LimitsConfig::default());
     }
 
     #[test]
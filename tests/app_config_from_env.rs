@@ -1,7 +1,12 @@
 mod app_config_from_env {
-    use fregate::{bootstrap, AppConfig, ConfigSource, Empty};
+    use fregate::config::FileFormat;
+    use fregate::error::Error;
+    use fregate::observability::Filter;
+    use fregate::{
+        bootstrap, AppConfig, ConfigSource, Empty, Validate, ValidationError, ValidationErrors,
+    };
     use serde::Deserialize;
-    use std::net::{IpAddr, Ipv6Addr};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
     use std::time::Duration;
 
     #[derive(Deserialize, Debug, PartialEq, Eq)]
@@ -88,6 +93,8 @@ mod app_config_from_env {
         std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_HEALTH", "///valid");
         std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_LIVE", "invalid");
         std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_READY", "");
+        std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_STARTUP", "/probe/startup");
+        std::env::set_var("MNGM_MANAGEMENT_ENDPOINTS_LOGLEVEL", "/probe/loglevel");
 
         let config: AppConfig =
             bootstrap([ConfigSource::EnvPrefix("MNGM")]).expect("Failed to build AppConfig");
@@ -98,6 +105,93 @@ mod app_config_from_env {
         assert_eq!(management_cfg.endpoints.health.as_ref(), "///valid");
         assert_eq!(management_cfg.endpoints.live.as_ref(), "/live");
         assert_eq!(management_cfg.endpoints.ready.as_ref(), "/ready");
+        assert_eq!(management_cfg.endpoints.startup.as_ref(), "/probe/startup");
+        assert_eq!(
+            management_cfg.endpoints.loglevel.as_ref(),
+            "/probe/loglevel"
+        );
+    }
+
+    #[test]
+    fn test_management_listener_from_env() {
+        std::env::set_var("MNGM_LISTENER_MANAGEMENT_HOST", "127.0.0.1");
+        std::env::set_var("MNGM_LISTENER_MANAGEMENT_PORT", "9000");
+        std::env::set_var(
+            "MNGM_LISTENER_MANAGEMENT_ENDPOINTS_METRICS",
+            "/probe/metrics",
+        );
+
+        let config =
+            AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("MNGM_LISTENER")]).unwrap();
+
+        let management_cfg = config.management_cfg;
+
+        assert_eq!(
+            management_cfg.host,
+            Some(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)))
+        );
+        assert_eq!(management_cfg.port, Some(9000));
+        assert_eq!(management_cfg.endpoints.metrics.as_ref(), "/probe/metrics");
+        assert_eq!(management_cfg.endpoints.health.as_ref(), "/health");
+    }
+
+    #[test]
+    fn test_shutdown_config_from_env() {
+        std::env::set_var("SHUTDOWN_CFG_SHUTDOWN_PRE_STOP_DELAY", "5000");
+        std::env::set_var("SHUTDOWN_CFG_SHUTDOWN_DRAIN_TIMEOUT", "30s");
+
+        let config =
+            AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("SHUTDOWN_CFG")]).unwrap();
+
+        let shutdown_cfg = config.shutdown_cfg;
+
+        assert_eq!(shutdown_cfg.pre_stop_delay, Duration::from_secs(5));
+        assert_eq!(shutdown_cfg.drain_timeout, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_http_config_from_env() {
+        std::env::set_var("HTTP_CFG_SERVER_HTTP_HTTP2_ONLY", "true");
+        std::env::set_var("HTTP_CFG_SERVER_HTTP_HTTP1_HEADER_READ_TIMEOUT", "1000");
+        std::env::set_var("HTTP_CFG_SERVER_HTTP_HTTP2_KEEP_ALIVE_INTERVAL", "20000");
+        std::env::set_var("HTTP_CFG_SERVER_HTTP_HTTP2_KEEP_ALIVE_TIMEOUT", "10000");
+        std::env::set_var("HTTP_CFG_SERVER_HTTP_HTTP2_MAX_CONCURRENT_STREAMS", "200");
+        std::env::set_var("HTTP_CFG_SERVER_HTTP_TCP_NODELAY", "true");
+        std::env::set_var("HTTP_CFG_SERVER_HTTP_TCP_KEEPALIVE", "1m");
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("HTTP_CFG")]).unwrap();
+
+        let http_cfg = config.http_cfg;
+
+        assert!(!http_cfg.http1_only);
+        assert!(http_cfg.http2_only);
+        assert_eq!(
+            http_cfg.http1_header_read_timeout,
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            http_cfg.http2_keep_alive_interval,
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(
+            http_cfg.http2_keep_alive_timeout,
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(http_cfg.http2_max_concurrent_streams, Some(200));
+        assert!(http_cfg.tcp_nodelay);
+        assert_eq!(http_cfg.tcp_keepalive, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_limits_config_from_env() {
+        std::env::set_var("LIMITS_CFG_SERVER_LIMITS_MAX_CONNECTIONS", "10k");
+        std::env::set_var("LIMITS_CFG_SERVER_LIMITS_MAX_IN_FLIGHT_REQUESTS", "1000");
+
+        let config =
+            AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("LIMITS_CFG")]).unwrap();
+
+        assert_eq!(config.limits_cfg.max_connections, Some(10000));
+        assert_eq!(config.limits_cfg.max_in_flight_requests, Some(1000));
     }
 
     #[test]
@@ -130,4 +224,269 @@ mod app_config_from_env {
 
         assert_eq!(config.port, 5678);
     }
+
+    #[test]
+    fn test_effective_config_from_env() {
+        std::env::set_var("DUMP_MANAGEMENT_CONFIG_ENABLED", "true");
+        std::env::set_var("DUMP_MANAGEMENT_CONFIG_SANITIZE", "pass");
+        std::env::set_var("DUMP_DB_PASSWORD", "secret");
+        std::env::set_var("DUMP_PORT", "5678");
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("DUMP")])
+            .expect("Failed to build AppConfig");
+        let config_dump = &config.management_cfg.config_dump;
+        assert!(config_dump.enabled);
+
+        let effective_cfg = &config.effective_cfg;
+        assert_eq!(effective_cfg.source("/port"), Some("env:DUMP"));
+        assert_eq!(effective_cfg.source("/host"), Some("default"));
+
+        let json = effective_cfg.to_json(&config_dump.sanitize);
+        assert_eq!(
+            json.pointer("/db/password/value"),
+            Some(&serde_json::json!("*****"))
+        );
+        assert!(!format!("{config:?}").contains("secret"));
+    }
+
+    #[test]
+    fn test_management_auth_from_env() {
+        std::env::set_var("AUTH_MANAGEMENT_AUTH_TOKEN", "secret");
+        std::env::set_var("AUTH_MANAGEMENT_AUTH_BASIC_USERNAME", "admin");
+        std::env::set_var("AUTH_MANAGEMENT_AUTH_BASIC_PASSWORD", "password");
+        std::env::set_var("AUTH_MANAGEMENT_AUTH_ALLOWED_IPS", "127.0.0.1, 10.0.0.0/8");
+        std::env::set_var("AUTH_MANAGEMENT_AUTH_EXEMPT_PROBES", "false");
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("AUTH")])
+            .expect("Failed to build AppConfig");
+        let auth = &config.management_cfg.auth;
+
+        assert_eq!(auth.token.as_deref(), Some("secret"));
+        assert_eq!(auth.basic_username.as_deref(), Some("admin"));
+        assert_eq!(auth.basic_password.as_deref(), Some("password"));
+        assert_eq!(
+            auth.allowed_ips,
+            Some(vec![
+                "127.0.0.1/32".parse().unwrap(),
+                "10.0.0.0/8".parse().unwrap()
+            ])
+        );
+        assert!(!auth.exempt_probes);
+        assert!(!format!("{auth:?}").contains("secret"));
+    }
+
+    #[test]
+    fn test_numeric_management_secrets_from_env() {
+        std::env::set_var("NUMAUTH_MANAGEMENT_AUTH_TOKEN", "42");
+        std::env::set_var("NUMAUTH_MANAGEMENT_AUTH_BASIC_USERNAME", "admin");
+        std::env::set_var("NUMAUTH_MANAGEMENT_AUTH_BASIC_PASSWORD", "1234");
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("NUMAUTH")])
+            .expect("Failed to build AppConfig");
+        let auth = &config.management_cfg.auth;
+
+        assert_eq!(auth.token.as_deref(), Some("42"));
+        assert_eq!(auth.basic_password.as_deref(), Some("1234"));
+    }
+
+    #[test]
+    fn test_invalid_management_auth_from_env() {
+        std::env::set_var("BADAUTH_MANAGEMENT_AUTH_BASIC_USERNAME", "admin");
+        let error = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("BADAUTH")])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("basic.password"), "{error}");
+
+        std::env::remove_var("BADAUTH_MANAGEMENT_AUTH_BASIC_USERNAME");
+        std::env::set_var("BADAUTH_MANAGEMENT_AUTH_ALLOWED_IPS", "127.0.0.1, invalid");
+        let error = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("BADAUTH")])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("`invalid`"), "{error}");
+    }
+
+    #[derive(Deserialize, Debug)]
+    pub struct Workers {
+        workers: usize,
+    }
+
+    impl Validate for Workers {
+        fn validate(&self, errors: &mut ValidationErrors) {
+            if self.workers == 0 {
+                errors.add("workers", "must be positive");
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_validation_from_env() {
+        std::env::set_var("STRICT_LOG_LEVEL", "info,[=");
+        std::env::set_var("STRICT_MANAGEMENT_ENDPOINTS_HEALTH", "health");
+        std::env::set_var("STRICT_SERVER_HTTP_TCP_KEEPALIVE", "-1");
+        std::env::set_var("STRICT_WORKERS", "0");
+
+        let builder = || {
+            AppConfig::<Workers>::builder()
+                .add_default()
+                .add_env_prefixed("STRICT")
+                .validate()
+        };
+
+        let error = builder().strict(true).build().unwrap_err();
+        let Error::ValidationError(errors) = error else {
+            panic!("unexpected error: {error}");
+        };
+        let errors = errors
+            .iter()
+            .map(|err| (err.key.as_str(), err.source.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                ("log.level", Some("env:STRICT")),
+                ("server.http.tcp.keepalive", Some("env:STRICT")),
+                ("management.endpoints.health", Some("env:STRICT")),
+            ]
+        );
+
+        // invalid values of built-in sections fail deserialization regardless of strict mode.
+        std::env::remove_var("STRICT_MANAGEMENT_ENDPOINTS_HEALTH");
+        std::env::remove_var("STRICT_SERVER_HTTP_TCP_KEEPALIVE");
+        let error = builder().build().unwrap_err();
+        let Error::ValidationError(errors) = error else {
+            panic!("unexpected error: {error}");
+        };
+        let errors = errors.iter().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [ValidationError {
+                key: "workers".to_owned(),
+                source: Some("env:STRICT".to_owned()),
+                message: "must be positive".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_placeholders_from_env() {
+        let secret = std::env::temp_dir().join("fregate_placeholders_from_env");
+        std::fs::write(&secret, "token\n").expect("Failed to write secret");
+
+        std::env::set_var(
+            "SECRET_MANAGEMENT_AUTH_TOKEN",
+            format!("${{file:{}}}", secret.display()),
+        );
+        std::env::set_var("SECRET_DB_PASSWORD", "${env:SECRET_SOURCE}");
+        std::env::set_var("SECRET_SOURCE", "password");
+        std::env::set_var("SECRET_PORT", "${env:PLACEHOLDER_PORT}");
+        std::env::set_var("PLACEHOLDER_PORT", "9100");
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("SECRET")])
+            .expect("Failed to build AppConfig");
+        assert_eq!(config.management_cfg.auth.token.as_deref(), Some("token"));
+        assert_eq!(config.port, 9100);
+
+        let effective_cfg = &config.effective_cfg;
+        assert!(effective_cfg.is_sensitive("/management/auth/token"));
+        assert!(effective_cfg.is_sensitive("/db/password"));
+        assert!(!effective_cfg.is_sensitive("/source"));
+
+        let json = effective_cfg.to_json(&Filter::Set(Default::default()));
+        assert_eq!(
+            json.pointer("/db/password/value"),
+            Some(&serde_json::json!("*****"))
+        );
+
+        std::env::set_var("SECRET_DB_PASSWORD", "${file:/not/existing/file}");
+        let error = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("SECRET")]).unwrap_err();
+        let Error::ValidationError(errors) = error else {
+            panic!("unexpected error: {error}");
+        };
+        let keys = errors
+            .iter()
+            .map(|err| err.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["db.password"]);
+    }
+
+    #[test]
+    fn test_units_from_env() {
+        std::env::set_var("UNITS_LOG_MSG_LENGTH", "4KiB");
+        std::env::set_var("UNITS_LOGGING_INTERVAL", "1h");
+        std::env::set_var("UNITS_LOGGING_MAX_FILE_SIZE", "100MiB");
+        std::env::set_var("UNITS_LOGGING_MAX_HISTORY", "120");
+
+        // `_` is env separator, so `update_interval` can't be set with env variable.
+        let config = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_str(
+                "[server.metrics]\nupdate_interval = \"2s\"",
+                FileFormat::Toml,
+            )
+            .add_env_prefixed("UNITS")
+            .strict(true)
+            .build()
+            .expect("Failed to build AppConfig");
+
+        let observability_cfg = &config.observability_cfg;
+        assert_eq!(
+            observability_cfg.metrics_update_interval,
+            Duration::from_secs(2)
+        );
+
+        let logger_config = &observability_cfg.logger_config;
+        assert_eq!(logger_config.msg_length, Some(4096));
+        assert_eq!(
+            logger_config.logging_interval,
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(logger_config.logging_max_file_size, Some(100 * 1024 * 1024));
+        assert_eq!(
+            logger_config.logging_max_history,
+            Some(Duration::from_secs(120))
+        );
+
+        std::env::set_var("UNITS_LOGGING_MAX_FILE_SIZE", "100 apples");
+        let error = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_env_prefixed("UNITS")
+            .strict(true)
+            .build()
+            .unwrap_err();
+        let Error::ValidationError(errors) = error else {
+            panic!("unexpected error: {error}");
+        };
+        let keys = errors
+            .iter()
+            .map(|err| err.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["logging.max.file.size"]);
+    }
+
+    #[test]
+    fn test_invalid_shutdown_config_from_env() {
+        std::env::set_var("SHUTDOWN_INVALID_SHUTDOWN_DRAIN_TIMEOUT", "30 apples");
+
+        let error = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("SHUTDOWN_INVALID")])
+            .unwrap_err();
+        assert!(error.to_string().contains("/drain/timeout"), "{error}");
+    }
+
+    #[test]
+    fn test_invalid_http_config_from_env() {
+        std::env::set_var("HTTP_INVALID_SERVER_HTTP_TCP_NODELAY", "yes");
+
+        let error =
+            AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("HTTP_INVALID")]).unwrap_err();
+        assert!(error.to_string().contains("/tcp/nodelay"), "{error}");
+    }
+
+    #[test]
+    fn test_invalid_limits_config_from_env() {
+        std::env::set_var("LIMITS_INVALID_SERVER_LIMITS_MAX_CONNECTIONS", "-1");
+
+        let error =
+            AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("LIMITS_INVALID")]).unwrap_err();
+        assert!(error.to_string().contains("/max/connections"), "{error}");
+    }
 }
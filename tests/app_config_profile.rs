@@ -0,0 +1,45 @@
+mod app_config_profile {
+    use fregate::{AppConfig, ConfigSource, Empty, CONFIG_DIR_ENV, PROFILE_ENV};
+
+    #[test]
+    fn test_profile_layering() {
+        let dir = std::env::temp_dir().join(format!("fregate-profile-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("Failed to create dir");
+        std::fs::write(dir.join("app.yaml"), "port: 1111\nservice:\n  name: base\n")
+            .expect("Failed to write file");
+        std::fs::write(dir.join("app-dev.yaml"), "port: 2222\n").expect("Failed to write file");
+
+        let dir_str = dir.to_str().expect("Invalid path");
+        std::env::set_var(CONFIG_DIR_ENV, dir_str);
+        std::env::set_var(PROFILE_ENV, "dev");
+
+        let config = AppConfig::<Empty>::load_from([]).expect("Failed to build AppConfig");
+        assert_eq!(config.port, 2222);
+        assert_eq!(config.observability_cfg.service_name, "base");
+        assert_eq!(
+            config.effective_cfg.layers(),
+            [
+                "default".to_owned(),
+                "env:OTEL".to_owned(),
+                format!("file:{}", dir.join("app.yaml").display()),
+                format!("file:{}", dir.join("app-dev.yaml").display()),
+            ]
+        );
+
+        std::fs::write(dir.join("app-local.yaml"), "port: 3333\n").expect("Failed to write file");
+        std::env::set_var("PROFILE_PORT", "4444");
+        let config = AppConfig::<Empty>::load_from([]).expect("Failed to build AppConfig");
+        assert_eq!(config.port, 3333);
+
+        let config = AppConfig::<Empty>::load_from([ConfigSource::EnvPrefix("PROFILE")])
+            .expect("Failed to build AppConfig");
+        assert_eq!(config.port, 4444);
+
+        std::env::set_var(PROFILE_ENV, "prod");
+        assert!(AppConfig::<Empty>::load_from([]).is_err());
+
+        std::env::remove_var(PROFILE_ENV);
+        let config = AppConfig::<Empty>::load_from([]).expect("Failed to build AppConfig");
+        assert_eq!(config.port, 8000);
+    }
+}
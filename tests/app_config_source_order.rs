@@ -19,4 +19,31 @@ mod app_config_source_order {
 
         assert_eq!(config.port, 9999);
     }
+
+    #[test]
+    fn test_args_have_highest_precedence() {
+        std::env::set_var("ARGS_PORT", "9999");
+        std::env::set_var("ARGS_LOG_LEVEL", "warn");
+
+        let config = AppConfig::<Empty>::builder()
+            .add_default()
+            .add_env_prefixed("ARGS")
+            .add_args_from([
+                "--config",
+                "./tests/resources/test_conf.toml",
+                "--log.level=debug",
+                "--management.auth.exempt.probes=false",
+            ])
+            .build()
+            .expect("Failed to build AppConfig");
+
+        assert_eq!(config.port, 8888);
+        assert_eq!(config.observability_cfg.logger_config.log_level, "debug");
+        assert!(!config.management_cfg.auth.exempt_probes);
+        assert_eq!(config.effective_cfg.source("/log/level"), Some("args"));
+        assert_eq!(
+            config.effective_cfg.source("/port"),
+            Some("file:./tests/resources/test_conf.toml")
+        );
+    }
 }
@@ -0,0 +1,190 @@
+mod application_lifecycle {
+    use fregate::listener::ListenerAddr;
+    use fregate::{AppConfig, Application};
+    use hyper::{Client, StatusCode};
+    use serde::Deserialize;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn hooks_called_in_order() {
+        let config = AppConfig {
+            port: 0,
+            ..AppConfig::default()
+        };
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let (sender, receiver) = oneshot::channel::<()>();
+
+        let first = events.clone();
+        let second = events.clone();
+        let third = events.clone();
+
+        let app = Application::new(config)
+            .with_shutdown(async move {
+                let _ = receiver.await;
+            })
+            .on_start(move || async move {
+                first.lock().unwrap().push("start");
+                let _ = sender.send(());
+            })
+            .on_shutdown(move || async move {
+                second.lock().unwrap().push("shutdown_1");
+            })
+            .on_shutdown(move || async move {
+                third.lock().unwrap().push("shutdown_2");
+            });
+
+        tokio::time::timeout(Duration::from_secs(5), app.serve())
+            .await
+            .expect("Application must stop on custom shutdown")
+            .expect("Application must stop without errors");
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["start", "shutdown_1", "shutdown_2"]
+        );
+    }
+
+    static EVENTS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+    #[derive(Debug, Deserialize)]
+    struct DropProbe {}
+
+    impl Drop for DropProbe {
+        fn drop(&mut self) {
+            EVENTS.lock().unwrap().push("config_dropped");
+        }
+    }
+
+    #[tokio::test]
+    async fn config_dropped_after_shutdown_hooks() {
+        let mut config = AppConfig::<DropProbe>::builder()
+            .add_default()
+            .build()
+            .unwrap();
+        config.port = 0;
+
+        let running = Application::new(config)
+            .on_shutdown(|| async {
+                EVENTS.lock().unwrap().push("on_shutdown");
+            })
+            .bind()
+            .await
+            .unwrap();
+
+        running.shutdown();
+        tokio::time::timeout(Duration::from_secs(5), running)
+            .await
+            .expect("Application must stop on shutdown")
+            .expect("Application must stop without errors");
+
+        assert_eq!(
+            *EVENTS.lock().unwrap(),
+            vec!["on_shutdown", "config_dropped"]
+        );
+    }
+
+    #[tokio::test]
+    async fn bind_returns_running_application() {
+        let config = AppConfig {
+            port: 0,
+            ..AppConfig::default()
+        };
+
+        let running = Application::new(config).bind().await.unwrap();
+        let ListenerAddr::Tcp(addr) = running.local_addr().clone() else {
+            panic!("Application must be bound to TCP socket");
+        };
+        assert_ne!(addr.port(), 0);
+        assert!(running.management_addr().is_none());
+
+        let uri = format!("http://{addr}/health").parse().unwrap();
+        let response = Client::new().get(uri).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        running.shutdown();
+        tokio::time::timeout(Duration::from_secs(5), running)
+            .await
+            .expect("Application must stop on shutdown")
+            .expect("Application must stop without errors");
+    }
+
+    #[tokio::test]
+    async fn dropping_handle_stops_application() {
+        let config = AppConfig {
+            port: 0,
+            ..AppConfig::default()
+        };
+        let (sender, receiver) = oneshot::channel::<()>();
+
+        let running = Application::new(config)
+            .on_shutdown(move || async move {
+                let _ = sender.send(());
+            })
+            .bind()
+            .await
+            .unwrap();
+        drop(running);
+
+        tokio::time::timeout(Duration::from_secs(5), receiver)
+            .await
+            .expect("Application must shut down once handle is dropped")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn bind_rejects_conflicting_http_protocols() {
+        let mut config = AppConfig {
+            port: 0,
+            ..AppConfig::default()
+        };
+        config.http_cfg.http1_only = true;
+        config.http_cfg.http2_only = true;
+
+        assert!(Application::new(config).bind().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn warm_up_gates_startup_and_readiness() {
+        let config = AppConfig {
+            port: 0,
+            ..AppConfig::default()
+        };
+        let (release, warmed_up) = oneshot::channel::<()>();
+
+        let running = Application::new(config)
+            .warm_up(async move {
+                let _ = warmed_up.await;
+            })
+            .bind()
+            .await
+            .unwrap();
+        let ListenerAddr::Tcp(addr) = running.local_addr().clone() else {
+            panic!("Application must be bound to TCP socket");
+        };
+
+        let client = Client::new();
+        let status = |path: &'static str| {
+            let client = client.clone();
+            async move {
+                let uri = format!("http://{addr}{path}").parse().unwrap();
+                client.get(uri).await.unwrap().status()
+            }
+        };
+
+        assert_eq!(status("/startup").await, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(status("/ready").await, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(status("/live").await, StatusCode::OK);
+
+        release.send(()).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(status("/startup").await, StatusCode::OK);
+        assert_eq!(status("/ready").await, StatusCode::OK);
+
+        running.shutdown();
+        running.await.unwrap();
+    }
+}
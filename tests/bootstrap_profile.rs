@@ -0,0 +1,29 @@
+mod bootstrap_profile {
+    use fregate::{bootstrap, AppConfig, CONFIG_DIR_ENV, PROFILE_ENV};
+
+    #[tokio::test]
+    async fn bootstrap_adds_active_profile() {
+        let dir =
+            std::env::temp_dir().join(format!("fregate-bootstrap-profile-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("Failed to create dir");
+        std::fs::write(dir.join("app.yaml"), "port: 1111\n").expect("Failed to write file");
+        std::fs::write(dir.join("app-dev.yaml"), "port: 2222\n").expect("Failed to write file");
+
+        std::env::set_var(CONFIG_DIR_ENV, dir.to_str().expect("Invalid path"));
+        std::env::set_var(PROFILE_ENV, "dev");
+
+        let config: AppConfig = bootstrap([]).expect("Failed to bootstrap");
+        assert_eq!(config.port, 2222);
+        assert_eq!(
+            config.effective_cfg.layers(),
+            [
+                "default".to_owned(),
+                "env:OTEL".to_owned(),
+                format!("file:{}", dir.join("app.yaml").display()),
+                format!("file:{}", dir.join("app-dev.yaml").display()),
+            ]
+        );
+
+        std::fs::remove_dir_all(dir).expect("Failed to remove dir");
+    }
+}
@@ -0,0 +1,67 @@
+mod limits {
+    use fregate::axum::{routing::get, Router};
+    use fregate::listener::ListenerAddr;
+    use fregate::{AppConfig, Application};
+    use hyper::{Body, Client, Request, StatusCode};
+    use std::time::Duration;
+    use tokio::sync::Notify;
+
+    #[tokio::test]
+    async fn rejects_requests_above_in_flight_limit() {
+        let mut config = AppConfig {
+            port: 0,
+            ..AppConfig::default()
+        };
+        config.limits_cfg.max_in_flight_requests = Some(1);
+
+        let release = std::sync::Arc::new(Notify::new());
+        let notified = release.clone();
+        let router = Router::new().route(
+            "/slow",
+            get(move || async move {
+                notified.notified().await;
+                "done"
+            }),
+        );
+
+        let running = Application::new(config)
+            .router(router)
+            .bind()
+            .await
+            .unwrap();
+        let ListenerAddr::Tcp(addr) = running.local_addr().clone() else {
+            panic!("Application must be bound to TCP socket");
+        };
+
+        let client = Client::new();
+        let slow = tokio::spawn(client.get(format!("http://{addr}/slow").parse().unwrap()));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let response = client
+            .get(format!("http://{addr}/slow").parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let request = Request::post(format!("http://{addr}/slow"))
+            .header("content-type", "application/grpc")
+            .body(Body::empty())
+            .unwrap();
+        let response = client.request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["grpc-status"], "8");
+
+        // management endpoints are not limited.
+        let response = client
+            .get(format!("http://{addr}/health").parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        release.notify_one();
+        assert_eq!(slow.await.unwrap().unwrap().status(), StatusCode::OK);
+
+        running.shutdown();
+        running.await.unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+mod listener {
+    use fregate::listener::{Listener, ListenerAddr};
+    use fregate::{AppConfig, Application};
+    use hyper::{Body, Client, Request, StatusCode};
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn serves_on_pre_opened_tcp_listener() {
+        let tcp = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = tcp.local_addr().unwrap().port();
+
+        let (sender, receiver) = oneshot::channel::<()>();
+        let app = Application::new(AppConfig::default())
+            .listener(Listener::from(tcp))
+            .with_shutdown(async move {
+                let _ = receiver.await;
+            });
+        let handle = tokio::spawn(app.serve());
+
+        let uri = format!("http://127.0.0.1:{port}/health");
+        let response = Client::new().get(uri.parse().unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        sender.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn serves_on_unix_socket() {
+        let path = std::env::temp_dir().join(format!("fregate-{}.sock", std::process::id()));
+        let listener = Listener::bind_unix(&path).unwrap();
+        assert_eq!(
+            listener.local_addr().unwrap(),
+            ListenerAddr::Unix(path.clone())
+        );
+
+        let (sender, receiver) = oneshot::channel::<()>();
+        let app = Application::new(AppConfig::default())
+            .listener(listener)
+            .with_shutdown(async move {
+                let _ = receiver.await;
+            });
+        let handle = tokio::spawn(app.serve());
+
+        let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        let (mut request_sender, connection) =
+            hyper::client::conn::handshake(stream).await.unwrap();
+        tokio::spawn(connection);
+
+        let request = Request::get("/health").body(Body::empty()).unwrap();
+        let response = request_sender.send_request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        drop(request_sender);
+        sender.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn rejects_allowed_ips_on_unix_socket() {
+        let path = std::env::temp_dir().join(format!("fregate-auth-{}.sock", std::process::id()));
+        let listener = Listener::bind_unix(&path).unwrap();
+
+        let mut config = AppConfig::default();
+        config.management_cfg.auth.allowed_ips = Some(vec!["127.0.0.1/32".parse().unwrap()]);
+
+        let result = Application::new(config).listener(listener).bind().await;
+        assert!(result.is_err());
+
+        let _ = std::fs::remove_file(path);
+    }
+}
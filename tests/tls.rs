@@ -1,6 +1,6 @@
 #[cfg(feature = "tls")]
 mod tls {
-    use fregate::{error::Error, AppConfig, Application, Empty};
+    use fregate::{listener::ListenerAddr, AppConfig, Application, Empty};
     use hyper::{client::HttpConnector, Client, StatusCode, Uri};
     use hyper_rustls::{ConfigBuilderExt, HttpsConnector, HttpsConnectorBuilder};
     use rustls::{
@@ -8,15 +8,10 @@ mod tls {
         Certificate, ClientConfig, ServerName,
     };
     use std::{
-        io::ErrorKind,
         str::FromStr,
         sync::Arc,
         time::{Duration, SystemTime},
     };
-    use tokio::time::timeout;
-
-    const ROOTLES_PORT: u16 = 1025;
-    const MAX_PORT: u16 = u16::MAX;
 
     const TLS_KEY_FULL_PATH: &str = concat!(
         env!("CARGO_MANIFEST_DIR"),
@@ -37,44 +32,16 @@ mod tls {
             .build()
             .unwrap();
 
-        let mut free_port = None;
+        config.port = 0;
         let tls_timeout = config.tls.handshake_timeout;
 
-        for port in ROOTLES_PORT..MAX_PORT {
-            config.port = port;
-
-            let next_config = config.clone();
-            let application_handle = timeout(
-                Duration::from_secs(1),
-                tokio::task::spawn(async move { Application::new(next_config).serve_tls().await }),
-            )
-            .await;
-
-            match application_handle {
-                Err(_elapsed) => {
-                    free_port = Some(port);
-                    break;
-                }
-                Ok(Err(err)) => {
-                    panic!("Unexpected error: `{err}`.");
-                }
-                Ok(Ok(Err(Error::IoError(err)))) => {
-                    if err.kind() == ErrorKind::AddrInUse {
-                        continue;
-                    } else {
-                        panic!("Unexpected error: `{err}`.");
-                    }
-                }
-                Ok(Ok(Err(err))) => {
-                    panic!("Unexpected error: `{err}`.");
-                }
-                Ok(Ok(Ok(()))) => unreachable!("impossible"),
-            }
-        }
-
-        tokio::time::sleep(Duration::from_millis(200)).await;
+        let application = Application::new(config).bind_tls().await.unwrap();
+        let port = match application.local_addr() {
+            ListenerAddr::Tcp(addr) => addr.port(),
+            addr => panic!("Unexpected address: `{addr}`."),
+        };
 
-        (free_port.expect("No free ports are available"), tls_timeout)
+        (port, tls_timeout)
     }
 
     fn build_client() -> Client<HttpsConnector<HttpConnector>> {
@@ -109,7 +76,6 @@ mod tls {
         Client::builder().http2_only(true).build(https)
     }
 
-    #[ignore]
     #[tokio::test]
     async fn test_https_request() {
         let (port, _) = start_server().await;
@@ -127,7 +93,6 @@ mod tls {
         assert_eq!(body.as_ref(), b"OK");
     }
 
-    #[ignore]
     #[tokio::test]
     async fn test_http_request() {
         let (port, tls_timeout) = start_server().await;